@@ -1,5 +1,8 @@
 mod request;
 mod method;
+mod future;
+mod builder;
+mod openapi;
 
 /// 宏输入类型枚举
 pub enum MacroForm {
@@ -0,0 +1,166 @@
+//! `#[builder]`：为路由方法生成链式请求构造器
+//!
+//! `client.search_builder().q(..).age(30).send().await` 等价于按位置调用 `client.search(..)`。
+//! `Option`、`Vec`/切片与按值传入的 `#[options]` 参数可省略；其余参数以类型状态跟踪，
+//! 未全部提供时构造器上没有 `send()`，在编译期报错。
+
+use crate::parser::{Cardinality, MethodMeta, ParamKind};
+use crate::util::{future_kind, future_output, FutureKind};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{GenericParam, ItemTrait, Lifetime, ReturnType, Type};
+
+/// 构造器中的一个字段（对应方法的一个参数）
+struct Field {
+    ident: syn::Ident,
+    ty: Type,               // 绑定生命周期后的参数类型
+    setter_ty: Type,        // setter 接收的类型：`Option<T>` 参数为 `T`
+    wrap_some: bool,        // setter 是否包裹为 `Some(..)`
+    state: Option<syn::Ident>, // 必填参数的类型状态参数
+}
+
+pub fn expand_builder(meta: &MethodMeta, trait_item: &ItemTrait, client_ident: &syn::Ident) -> syn::Result<TokenStream> {
+    let sig = &meta.sig;
+    let lt = Lifetime::new(super::future::DYN_LIFETIME, proc_macro2::Span::call_site());
+
+    // dyn 模式引入的 '__waygate 与构造器的生命周期一致，其余泛型参数不支持
+    if let Some(param) = sig.generics.params.iter().find(|p| !matches!(p, GenericParam::Lifetime(def) if def.lifetime == lt)) {
+        return Err(syn::Error::new_spanned(
+            param,
+            "#[builder] methods cannot declare generic parameters or named lifetimes",
+        ));
+    }
+    let mutability = match sig.receiver() {
+        Some(receiver) if receiver.reference.is_some() => receiver.mutability,
+        _ => {
+            return Err(syn::Error::new_spanned(sig, "#[builder] methods must take `&self` or `&mut self`"));
+        }
+    };
+
+    let mut output: Type = match (future_kind(sig), &sig.output) {
+        (_, ReturnType::Default) => syn::parse_quote!(()),
+        (FutureKind::Async, ReturnType::Type(_, ty)) => (**ty).clone(),
+        (_, ReturnType::Type(_, ty)) => future_output(ty)
+            .cloned()
+            .ok_or_else(|| syn::Error::new_spanned(ty, "cannot determine the future output type"))?,
+    };
+    super::future::bind_elided(&mut output, &lt, "#[builder]")?;
+
+    let mut fields = Vec::new();
+    for (index, p) in meta.params.iter().enumerate() {
+        let Some(ty) = &p.ty else { continue; };
+        let mut ty = ty.clone();
+        super::future::bind_elided(&mut ty, &lt, "#[builder]")?;
+        let by_value_options = matches!(p.kind, ParamKind::Options) && !matches!(ty, Type::Reference(_));
+        let (setter_ty, wrap_some, state) = match p.cardinality {
+            Cardinality::Option => (option_inner(&ty).cloned().unwrap_or_else(|| ty.clone()), true, None),
+            Cardinality::Many => (ty.clone(), false, None),
+            Cardinality::Single if by_value_options => (ty.clone(), false, None),
+            Cardinality::Single => (ty.clone(), false, Some(format_ident!("__S{}", index))),
+        };
+        fields.push(Field { ident: p.ident.clone(), ty, setter_ty, wrap_some, state });
+    }
+
+    let vis = &trait_item.vis;
+    let trait_ident = &trait_item.ident;
+    let method_ident = &meta.sig_ident;
+    let builder_ident = format_ident!("{}{}Builder", trait_ident, pascal_case(&method_ident.to_string()));
+    let builder_fn = format_ident!("{}_builder", method_ident);
+    let cfgs = meta.attrs.iter().filter(|a| a.path().is_ident("cfg")).collect::<Vec<_>>();
+
+    let states = fields.iter().filter_map(|f| f.state.as_ref()).collect::<Vec<_>>();
+    let field_decls = fields.iter().map(|f| {
+        let ident = &f.ident;
+        match &f.state {
+            Some(state) => quote! { #ident: #state },
+            None => {
+                let ty = &f.ty;
+                quote! { #ident: #ty }
+            }
+        }
+    });
+    let initial = fields.iter().map(|f| {
+        let ident = &f.ident;
+        match &f.state {
+            Some(_) => quote! { #ident: ::waygate::builder::Missing },
+            None => quote! { #ident: ::core::default::Default::default() },
+        }
+    });
+    let missing = states.iter().map(|_| quote! { ::waygate::builder::Missing });
+
+    // setter：必填参数改变对应的类型状态，其余参数保持构造器类型不变
+    let setters = fields.iter().map(|f| {
+        let ident = &f.ident;
+        let setter_ty = &f.setter_ty;
+        let value = if f.wrap_some { quote! { ::core::option::Option::Some(#ident) } } else { quote! { #ident } };
+        let next_states = states.iter().map(|s| match &f.state {
+            Some(own) if own == *s => {
+                let ty = &f.ty;
+                quote! { #ty }
+            }
+            _ => quote! { #s },
+        });
+        let others = fields.iter().filter(|o| o.ident != f.ident).map(|o| {
+            let other = &o.ident;
+            quote! { #other: self.#other }
+        });
+        quote! {
+            pub fn #ident(self, #ident: #setter_ty) -> #builder_ident<#lt, #(#next_states),*> {
+                #builder_ident { __client: self.__client, #(#others,)* #ident: #value }
+            }
+        }
+    });
+
+    let ready = fields.iter().filter_map(|f| f.state.as_ref().map(|_| &f.ty));
+    let args = fields.iter().map(|f| &f.ident);
+    let doc = format!("[`{}::{}`] 的链式请求构造器，由 `{}::{}()` 创建", trait_ident, method_ident, client_ident, builder_fn);
+
+    Ok(quote! {
+        #(#cfgs)*
+        #[doc = #doc]
+        #[must_use = "call `.send()` to perform the request"]
+        #vis struct #builder_ident<#lt, #(#states),*> {
+            __client: &#lt #mutability #client_ident,
+            #(#field_decls,)*
+        }
+
+        #(#cfgs)*
+        impl #client_ident {
+            pub fn #builder_fn(&#mutability self) -> #builder_ident<'_, #(#missing),*> {
+                #builder_ident { __client: self, #(#initial,)* }
+            }
+        }
+
+        #(#cfgs)*
+        impl<#lt, #(#states),*> #builder_ident<#lt, #(#states),*> {
+            #(#setters)*
+        }
+
+        #(#cfgs)*
+        impl<#lt> #builder_ident<#lt, #(#ready),*> {
+            pub async fn send(self) -> #output {
+                <#client_ident as #trait_ident>::#method_ident(self.__client, #(self.#args),*).await
+            }
+        }
+    })
+}
+
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let Type::Path(tp) = ty else { return None; };
+    let seg = tp.path.segments.last().filter(|seg| seg.ident == "Option")?;
+    let syn::PathArguments::AngleBracketed(args) = &seg.arguments else { return None; };
+    args.args.iter().find_map(|arg| match arg {
+        syn::GenericArgument::Type(t) => Some(t),
+        _ => None,
+    })
+}
+
+fn pascal_case(name: &str) -> String {
+    name.split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            chars.next().map_or_else(String::new, |c| c.to_ascii_uppercase().to_string() + chars.as_str())
+        })
+        .collect()
+}
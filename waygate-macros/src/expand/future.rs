@@ -0,0 +1,145 @@
+//! `#[request(send)]` / `#[request(dyn)]`：将 trait 中的 `async fn` 改写为返回显式 Future 的签名
+//!
+//! - `send`：`fn f(&self, ..) -> impl Future<Output = R> + Send`
+//! - `dyn`：`fn f<'__waygate>(&'__waygate self, ..) -> Pin<Box<dyn Future<Output = R> + Send + '__waygate>>`，
+//!   省略的引用生命周期统一绑定到 `'__waygate`，trait 可作为 `Box<dyn Api + Send + Sync>` 使用
+//!
+//! 带默认实现的方法体包裹为 `async move` 块，并要求 `Self: Sync` 以保证 Future 为 `Send`。
+
+use crate::parser::FutureMode;
+use quote::quote;
+use syn::visit_mut::VisitMut;
+use syn::{FnArg, GenericParam, ItemTrait, Lifetime, ReturnType, TraitItem, TraitItemFn, Type, parse_quote};
+
+pub(super) const DYN_LIFETIME: &str = "'__waygate";
+
+pub fn rewrite_trait(trait_item: &mut ItemTrait, mode: FutureMode) -> syn::Result<()> {
+    for item in &mut trait_item.items {
+        if let TraitItem::Fn(m) = item
+            && m.sig.asyncness.is_some()
+        {
+            rewrite_method(m, mode)?;
+        }
+    }
+    Ok(())
+}
+
+fn rewrite_method(m: &mut TraitItemFn, mode: FutureMode) -> syn::Result<()> {
+    m.sig.asyncness = None;
+    let output: Type = match &m.sig.output {
+        ReturnType::Default => parse_quote!(()),
+        ReturnType::Type(_, ty) => (**ty).clone(),
+    };
+
+    let future_ty: Type = match mode {
+        FutureMode::Send => parse_quote! {
+            impl ::core::future::Future<Output = #output> + ::core::marker::Send
+        },
+        FutureMode::Dyn => {
+            let lt = Lifetime::new(DYN_LIFETIME, proc_macro2::Span::call_site());
+            bind_lifetimes(m, &lt)?;
+            parse_quote! {
+                ::core::pin::Pin<::std::boxed::Box<
+                    dyn ::core::future::Future<Output = #output> + ::core::marker::Send + #lt
+                >>
+            }
+        }
+    };
+    m.sig.output = parse_quote!(-> #future_ty);
+
+    if let Some(body) = &m.default {
+        let future = quote! { async move #body };
+        let block = match mode {
+            FutureMode::Send => quote! {{ #future }},
+            FutureMode::Dyn => quote! {{ ::std::boxed::Box::pin(#future) }},
+        };
+        m.default = Some(syn::parse2(block)?);
+        m.sig.generics.make_where_clause().predicates.push(parse_quote!(Self: ::core::marker::Sync));
+    }
+    Ok(())
+}
+
+/// 引入 `'__waygate`：省略或匿名的引用生命周期替换为它，已声明的生命周期与类型参数须比它长
+fn bind_lifetimes(m: &mut TraitItemFn, lt: &Lifetime) -> syn::Result<()> {
+    let mut bounds: Vec<syn::WherePredicate> = Vec::new();
+    for param in &m.sig.generics.params {
+        match param {
+            GenericParam::Lifetime(def) => {
+                let name = &def.lifetime;
+                bounds.push(parse_quote!(#name: #lt));
+            }
+            GenericParam::Type(ty) => {
+                let name = &ty.ident;
+                bounds.push(parse_quote!(#name: #lt));
+            }
+            GenericParam::Const(_) => {}
+        }
+    }
+
+    let mut binder = BindElided { lifetime: lt.clone(), context: "#[request(dyn)]", error: None };
+    for input in &mut m.sig.inputs {
+        match input {
+            FnArg::Receiver(receiver) => {
+                if let Some((_, existing)) = &mut receiver.reference {
+                    match existing {
+                        Some(named) if named.ident != "_" => bounds.push(parse_quote!(#named: #lt)),
+                        _ => *existing = Some(lt.clone()),
+                    }
+                }
+                binder.visit_type_mut(&mut receiver.ty);
+            }
+            FnArg::Typed(pt) => binder.visit_type_mut(&mut pt.ty),
+        }
+    }
+    if let Some(err) = binder.error {
+        return Err(err);
+    }
+
+    m.sig.generics.params.insert(0, parse_quote!(#lt));
+    let where_clause = m.sig.generics.make_where_clause();
+    where_clause.predicates.extend(bounds);
+    where_clause.predicates.push(parse_quote!(Self: #lt));
+    Ok(())
+}
+
+/// 将类型中省略或匿名的引用生命周期绑定到 `lt`；`context` 用于 `impl Trait` 参数的报错
+pub(super) fn bind_elided(ty: &mut Type, lt: &Lifetime, context: &'static str) -> syn::Result<()> {
+    let mut binder = BindElided { lifetime: lt.clone(), context, error: None };
+    binder.visit_type_mut(ty);
+    binder.error.map_or(Ok(()), Err)
+}
+
+struct BindElided {
+    lifetime: Lifetime,
+    context: &'static str,
+    error: Option<syn::Error>,
+}
+
+impl VisitMut for BindElided {
+    fn visit_type_reference_mut(&mut self, r: &mut syn::TypeReference) {
+        if r.lifetime.as_ref().is_none_or(|l| l.ident == "_") {
+            r.lifetime = Some(self.lifetime.clone());
+        }
+        syn::visit_mut::visit_type_reference_mut(self, r);
+    }
+
+    fn visit_lifetime_mut(&mut self, l: &mut Lifetime) {
+        if l.ident == "_" {
+            *l = self.lifetime.clone();
+        }
+    }
+
+    // 函数指针与 Fn trait 中的生命周期属于其自身作用域，不改写
+    fn visit_type_bare_fn_mut(&mut self, _: &mut syn::TypeBareFn) {}
+
+    fn visit_parenthesized_generic_arguments_mut(&mut self, _: &mut syn::ParenthesizedGenericArguments) {}
+
+    fn visit_type_impl_trait_mut(&mut self, it: &mut syn::TypeImplTrait) {
+        if self.error.is_none() {
+            self.error = Some(syn::Error::new_spanned(
+                it,
+                format!("`impl Trait` parameters are not supported with {}; use a concrete type", self.context),
+            ));
+        }
+    }
+}
@@ -1,21 +1,30 @@
 use crate::parser::Cardinality;
-use crate::{http::method::Method, parser::ParamKind, parser::ParamMeta};
+use crate::{http::method::Method, parser::HeaderPolicy, parser::ParamKind, parser::ParamMeta, parser::ReturnShape, parser::StatusArm};
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::LitStr;
+use crate::util::{first_generic_arg, is_scalar_type, template_placeholders};
 
 #[derive(Clone)]
 pub struct MethodCtx {
     pub sig_ident: syn::Ident,
-    pub ok_ty: TokenStream,
+    pub shape: ReturnShape,                   // 返回值形态
+    pub error_body: Option<syn::Type>,        // 方法级优先于接口级的 error_body
+    pub not_found_as_none: bool,              // 404 返回 Ok(None)
+    pub status_arms: Vec<StatusArm>,          // #[status(...)] 状态码到枚举变体的映射
     pub method: Method,
     pub endpoint: Option<String>,
+    pub host: Option<String>,                 // 方法级具名主机
     pub path: String,
-    pub route_headers: Vec<(String, String)>, // 来自方法级 headers(...)
+    pub route_headers: Vec<(String, LitStr)>, // 来自方法级 headers(...)，值可含 {param} 模板
+    pub header_policy: HeaderPolicy,          // 同名请求头合并策略
+    pub static_query: Vec<(String, String)>,  // 接口级与方法级 query(...) 合并结果
     pub timeout_ms: Option<u64>,              // 方法级整体超时
     pub params: Vec<ParamMeta>,               // 统一参数模型
+    pub route_info: TokenStream,              // ::waygate::RouteInfo 字面量，传给拦截器
 }
 
+#[derive(Clone)]
 pub struct MethodExpander {
     ctx: MethodCtx,
     body: TokenStream, // 方法体代码
@@ -36,6 +45,17 @@ impl MethodExpander {
                 "The 'path' attribute cannot be empty.",
             ));
         }
+        // headers(...) 中的 {param} 必须引用方法参数
+        for (_, v) in &self.ctx.route_headers {
+            for name in template_placeholders(&v.value()) {
+                if !self.ctx.params.iter().any(|p| p.ident == name) {
+                    return Err(syn::Error::new(
+                        v.span(),
+                        format!("header template references unknown parameter `{}`", name),
+                    ));
+                }
+            }
+        }
         Ok(self)
     }
 
@@ -44,7 +64,9 @@ impl MethodExpander {
         let MethodCtx { path, .. } = &self.ctx;
 
         let path_lit = LitStr::new(path, self.ctx.sig_ident.span());
+        let route_info = &self.ctx.route_info;
         self.body.extend(quote! {
+            const __ROUTE: ::waygate::RouteInfo = #route_info;
             // 初始 path 字符串
             let mut __path = #path_lit.to_string();
         });
@@ -58,8 +80,9 @@ impl MethodExpander {
                 let ident = &p.ident;
                 let key = p.ident.to_string();
                 let key_lit = LitStr::new(&format!("{{{}}}", key), p.ident.span());
+                let name_lit = LitStr::new(&key, p.ident.span());
                 self.body.extend(quote! {
-                    __path = __path.replace(#key_lit, &::std::string::ToString::to_string(&#ident));
+                    __path = __path.replace(#key_lit, &::waygate::param::to_scalar(#name_lit, &#ident)?);
                 });
             }
         }
@@ -68,20 +91,38 @@ impl MethodExpander {
 
     // 在占位符替换完成后再生成 __url
     pub fn stage_build_url(mut self) -> Self {
-        let MethodCtx { endpoint, .. } = &self.ctx;
+        let MethodCtx { endpoint, host, .. } = &self.ctx;
 
+        // 端点解析与拼接失败均以 Error::UrlParse 返回，不 panic
         let url_stmt = if let Some(ep) = endpoint {
             let ep_lit = LitStr::new(ep, self.ctx.sig_ident.span());
             quote! {
-                let __base = ::url::Url::parse(#ep_lit)
-                    .expect("invalid endpoint in #[request(...)]");
-                let __url = __base.join(&__path)
-                    .expect("failed to join endpoint and path");
+                let __base = ::waygate::url::Url::parse(#ep_lit)?;
+                let __url = __base.join(&__path)?;
             }
-        } else {
+        } else if let Some(host) = host {
+            let host_lit = LitStr::new(host, self.ctx.sig_ident.span());
             quote! {
-                let __url = self.core.endpoint().join(&__path)
-                    .expect("failed to join endpoint and path");
+                let __url = self.core.join_host(#host_lit, &__path)?;
+            }
+        } else {
+            // #[host_var] 参数按 serde 序列化后参与端点模板渲染
+            let host_vars = self.ctx.params.iter().filter_map(|p| {
+                let ParamKind::HostVar { name } = &p.kind else { return None; };
+                let ident = &p.ident;
+                let name_lit = name
+                    .clone()
+                    .unwrap_or_else(|| LitStr::new(&p.ident.to_string(), p.ident.span()));
+                Some(quote! { (#name_lit, ::waygate::param::to_scalar(#name_lit, &#ident)?) })
+            }).collect::<Vec<_>>();
+            if host_vars.is_empty() {
+                quote! {
+                    let __url = self.core.join(&__path)?;
+                }
+            } else {
+                quote! {
+                    let __url = self.core.join_with_vars(&__path, &[#(#host_vars),*])?;
+                }
             }
         };
 
@@ -92,205 +133,171 @@ impl MethodExpander {
     pub fn stage_request_builder(mut self) -> Self {
         let method_tokens = self.ctx.method.to_token();
         self.body.extend(quote! {
-            let mut __req = self.core.client().request(#method_tokens, __url);
+            let mut __req = self.core.client().request(#method_tokens, __url.clone());
         });
         self
     }
 
     pub fn stage_apply_static_headers(mut self) -> Self {
+        // 方法级与参数级请求头先收集到 __headers，按策略合并后整体替换同名的客户端默认头；
+        // Append 策略下以客户端默认头（接口级）为基础追加
+        let policy = self.ctx.header_policy.to_token();
+        self.body.extend(match self.ctx.header_policy {
+            HeaderPolicy::Override => quote! {
+                let mut __headers = ::waygate::reqwest::header::HeaderMap::new();
+            },
+            HeaderPolicy::Append => quote! {
+                let mut __headers = self.core.default_headers().clone();
+            },
+        });
         // 方法属性里的静态 headers(...)
-        for (k, v) in &self.ctx.route_headers {
+        let pairs = self.ctx.route_headers.iter().map(|(k, v)| {
             let k_lit = LitStr::new(k, self.ctx.sig_ident.span());
-            let v_lit = LitStr::new(v, self.ctx.sig_ident.span());
-            self.body
-                .extend(quote! { __req = __req.header(#k_lit, #v_lit); });
+            let value = header_template_tokens(v);
+            quote! { ::waygate::param::header(#k_lit, &#value)? }
+        }).collect::<Vec<_>>();
+        if !pairs.is_empty() {
+            self.body.extend(quote! {
+                ::waygate::param::merge_headers(&mut __headers, [#(#pairs),*], #policy);
+            });
         }
         self
     }
 
     pub fn stage_apply_param_headers(mut self) -> Self {
-        // 参数级 #[header] 支持
+        // 参数级 #[header] / #[headers] 支持；名称或值非法时返回 Error::HeaderName / Error::HeaderValue
+        // 每个参数作为一个来源合并，Override 策略下替换接口级与方法级的同名请求头
+        let policy = self.ctx.header_policy.to_token();
+        for p in &self.ctx.params {
+            let ident = &p.ident;
+            match &p.kind {
+                ParamKind::Header { name } => {
+                    let name_lit = LitStr::new(&p.ident.to_string(), p.ident.span());
+                    let pair = quote! {
+                        ::waygate::param::header(#name, &::waygate::param::to_scalar(#name_lit, __v)?)
+                    };
+                    // Option => None 时跳过；Vec/切片 => 发送多个同名请求头
+                    self.body.extend(match p.cardinality {
+                        Cardinality::Single => quote! {
+                            { let __v = &#ident; ::waygate::param::merge_headers(&mut __headers, [#pair?], #policy); }
+                        },
+                        Cardinality::Option => quote! {
+                            if let Some(ref __v) = #ident { ::waygate::param::merge_headers(&mut __headers, [#pair?], #policy); }
+                        },
+                        Cardinality::Many => quote! {
+                            ::waygate::param::merge_headers(
+                                &mut __headers,
+                                #ident.iter().map(|__v| #pair).collect::<::waygate::Result<::std::vec::Vec<_>>>()?,
+                                #policy,
+                            );
+                        },
+                    });
+                }
+                ParamKind::Headers => {
+                    self.body.extend(quote! {
+                        ::waygate::param::merge_headers(
+                            &mut __headers,
+                            ::waygate::param::HeaderSource::header_pairs(&#ident)?,
+                            #policy,
+                        );
+                    });
+                }
+                _ => {}
+            }
+        }
+        self.body.extend(quote! {
+            __req = __req.headers(__headers);
+        });
+        self
+    }
+
+    pub fn stage_apply_cookies(mut self) -> Self {
+        // 参数级 #[cookie]：与 Cookie 存储中的值合并为一个 Cookie 请求头
+        let mut pushes = Vec::new();
         for p in &self.ctx.params {
-            if let ParamKind::Header { name } = &p.kind {
+            if let ParamKind::Cookie { name } = &p.kind {
                 let ident = &p.ident;
-                // 未命名则用形参名
-                let key = name
-                    .clone()
-                    .unwrap_or_else(|| LitStr::new(&p.ident.to_string(), p.ident.span()));
-                self.body.extend(quote! {
-                    __req = __req.header(#key, &#ident);
+                let name_lit = LitStr::new(&p.ident.to_string(), p.ident.span());
+                let key = name.clone().unwrap_or_else(|| name_lit.clone());
+                let push = quote! {
+                    __cookies.push((#key, ::waygate::param::to_scalar(#name_lit, __v)?));
+                };
+                pushes.push(match p.cardinality {
+                    Cardinality::Single => quote! { { let __v = &#ident; #push } },
+                    Cardinality::Option => quote! { if let Some(ref __v) = #ident { #push } },
+                    Cardinality::Many => quote! { for __v in #ident.iter() { #push } },
                 });
             }
         }
+        if !pushes.is_empty() {
+            self.body.extend(quote! {
+                let mut __cookies: ::std::vec::Vec<(&str, ::std::string::String)> = ::std::vec::Vec::new();
+                #(#pushes)*
+                if let Some(__value) = self.core.cookie_header(&__url, &__cookies)? {
+                    __req = __req.header(::waygate::reqwest::header::COOKIE, __value);
+                }
+            });
+        }
         self
     }
 
     pub fn stage_apply_query(mut self) -> Self {
-        use syn::Type;
-
         // 累积标量键值对，最后一次性调用 .query(&__query_vec)
         self.body.extend(quote! {
             let mut __query_vec: ::std::vec::Vec<(::std::borrow::Cow<'static, str>, ::std::string::String)> = ::std::vec::Vec::new();
         });
 
+        // 静态 query(...) 先入队，再追加参数级 #[query]
+        for (k, v) in &self.ctx.static_query {
+            let k_lit = LitStr::new(k, self.ctx.sig_ident.span());
+            let v_lit = LitStr::new(v, self.ctx.sig_ident.span());
+            self.body.extend(quote! {
+                __query_vec.push((::std::borrow::Cow::Borrowed(#k_lit), ::std::string::String::from(#v_lit)));
+            });
+        }
+
         for p in &self.ctx.params {
             if let ParamKind::Query { key } = &p.kind {
                 let ident = &p.ident;
+                let name_lit = LitStr::new(&p.ident.to_string(), p.ident.span());
                 // 未显式命名 => 使用形参名
-                let key_lit: LitStr = key
-                    .clone()
-                    .unwrap_or_else(|| LitStr::new(&p.ident.to_string(), p.ident.span()));
+                let key_lit: LitStr = key.clone().unwrap_or_else(|| name_lit.clone());
 
-                // 判断是否标量（String/str/数字/bool）
-                fn is_scalar_ty(ty: &Type) -> bool {
-                    match ty {
-                        Type::Path(tp) => {
-                            if let Some(seg) = tp.path.segments.last() {
-                                match seg.ident.to_string().as_str() {
-                                    "String" | "bool" |
-                                    "i8" | "i16" | "i32" | "i64" | "i128" | "isize" |
-                                    "u8" | "u16" | "u32" | "u64" | "u128" | "usize" |
-                                    "f32" | "f64" => true,
-                                    _ => false,
-                                }
-                            } else { false }
-                        }
-                        Type::Reference(r) => {
-                            matches!(&*r.elem, Type::Path(tp) if tp.path.is_ident("str"))
-                        }
-                        _ => false,
-                    }
-                }
-
-                // 取泛型内层类型（Option<T>/Vec<T>）
-                fn first_generic_arg(ty: &Type) -> Option<&Type> {
-                    if let Type::Path(tp) = ty {
-                        if let Some(seg) = tp.path.segments.last() {
-                            if let syn::PathArguments::AngleBracketed(args) = &seg.arguments {
-                                for arg in &args.args {
-                                    if let syn::GenericArgument::Type(t) = arg {
-                                        return Some(t);
-                                    }
-                                }
-                            }
-                        }
-                    }
-                    None
-                }
+                // 显式命名或已知标量类型 => 按 serde 序列化为单值；
+                // 未命名的复杂类型 => 直接 .query(&param) 展平字段
+                let as_scalar = key.is_some()
+                    || match (&p.ty, &p.cardinality) {
+                        (Some(ty), Cardinality::Single) => is_scalar_type(ty),
+                        (Some(ty), Cardinality::Option) => first_generic_arg(ty).is_some_and(is_scalar_type),
+                        // Vec<T>/切片：重复键，元素必须可序列化为单值
+                        (_, Cardinality::Many) => true,
+                        _ => true, // 无类型（如 self）不应出现到这里
+                    };
 
-                // 判断标量/复杂
-                let (is_scalar_single, is_scalar_inner) = match (&p.ty, p.cardinality.clone()) {
-                    (Some(ty), Cardinality::Single) => (is_scalar_ty(ty), true),
-                    (Some(ty), Cardinality::Option) => (first_generic_arg(ty).map(is_scalar_ty).unwrap_or(false), false),
-                    (Some(ty), Cardinality::Many)   => (first_generic_arg(ty).map(is_scalar_ty).unwrap_or(false), false),
-                    _ => (true, true), // 无类型（如 self）不应出现到这里，按标量跳过
-                };
-
-                let has_explicit_key = key.is_some();
-
-                match p.cardinality {
-                    Cardinality::Single => {
-                        if has_explicit_key {
-                            // 显式命名：只允许标量
-                            if is_scalar_single {
-                                self.body.extend(quote! {
-                                    __query_vec.push((::std::borrow::Cow::Borrowed(#key_lit), #ident.to_string()));
-                                });
-                            } else {
-                                self.body.extend(quote! {
-                                    compile_error!(concat!(
-                                        "Parameter `", stringify!(#ident),
-                                            "` uses #[query(\"…\")] with an explicit name, but its type is a struct/map (complex type). ",
-                                            "For complex types, remove the explicit name and use #[query] instead to expand its fields into multiple query parameters."
-                                    ));
-                                });
-                            }
-                        } else {
-                            // 未命名：标量 -> (ident, val)；复杂类型 -> 直接 .query(&param) 展平字段
-                            if is_scalar_single {
-                                self.body.extend(quote! {
-                                    __query_vec.push((::std::borrow::Cow::Borrowed(#key_lit), #ident.to_string()));
-                                });
-                            } else {
-                                self.body.extend(quote! {
-                                    __req = __req.query(&#ident);
-                                });
-                            }
+                let stmt = match (&p.cardinality, as_scalar) {
+                    (Cardinality::Single, true) => quote! {
+                        __query_vec.push((::std::borrow::Cow::Borrowed(#key_lit), ::waygate::param::to_scalar(#name_lit, &#ident)?));
+                    },
+                    (Cardinality::Single, false) => quote! {
+                        __req = __req.query(&#ident);
+                    },
+                    (Cardinality::Option, true) => quote! {
+                        if let Some(ref __v) = #ident {
+                            __query_vec.push((::std::borrow::Cow::Borrowed(#key_lit), ::waygate::param::to_scalar(#name_lit, __v)?));
                         }
-                    }
-                    Cardinality::Option => {
-                        if has_explicit_key {
-                            if is_scalar_inner {
-                                self.body.extend(quote! {
-                                    if let Some(ref __v) = #ident {
-                                        __query_vec.push((::std::borrow::Cow::Borrowed(#key_lit), __v.to_string()));
-                                    }
-                                });
-                            } else {
-                                self.body.extend(quote! {
-                                    compile_error!(concat!(
-                                        "Parameter `", stringify!(#ident),
-                                        "` is Option<complex type> and uses #[query(\"…\")]. ",
-                                        "For complex types, remove the explicit name and use #[query] instead; ",
-                                        "we will expand its fields when the value is Some. ",
-                                    ));
-                                });
-                            }
-                        } else {
-                            if is_scalar_inner {
-                                self.body.extend(quote! {
-                                    if let Some(ref __v) = #ident {
-                                        __query_vec.push((::std::borrow::Cow::Borrowed(#key_lit), __v.to_string()));
-                                    }
-                                });
-                            } else {
-                                self.body.extend(quote! {
-                                    if let Some(ref __v) = #ident {
-                                        __req = __req.query(__v);
-                                    }
-                                });
-                            }
+                    },
+                    (Cardinality::Option, false) => quote! {
+                        if let Some(ref __v) = #ident {
+                            __req = __req.query(__v);
                         }
-                    }
-                    Cardinality::Many => {
-                        if has_explicit_key {
-                            if is_scalar_inner {
-                                self.body.extend(quote! {
-                                    for __v in &#ident {
-                                        __query_vec.push((::std::borrow::Cow::Borrowed(#key_lit), __v.to_string()));
-                                    }
-                                });
-                            } else {
-                                self.body.extend(quote! {
-                                    compile_error!(concat!(
-                                        "Parameter `", stringify!(#ident),
-                                        "` is Vec<complex type> or slice and uses #[query(\"…\")]. ",
-                                        "Submitting complex elements with a single repeated key is not supported by default (ambiguous semantics). ",
-                                        "Consider using a single complex object with #[query], or implement a custom flattening strategy."
-                                    ));
-                                });
-                            }
-                        } else {
-                            if is_scalar_inner {
-                                self.body.extend(quote! {
-                                    for __v in &#ident {
-                                        __query_vec.push((::std::borrow::Cow::Borrowed(#key_lit), __v.to_string()));
-                                    }
-                                });
-                            } else {
-                                self.body.extend(quote! {
-                                    compile_error!(concat!(
-                                        "Parameter `", stringify!(#ident),
-                                        "` is Option<complex type> and uses #[query(\"…\")]. ",
-                                        "For complex types, remove the explicit name and use #[query] instead; ",
-                                        "we will expand its fields when the value is Some. ",
-                                        "Example: #[query] opt: Option<MyStruct>"
-                                    ));
-                                });
-                            }
+                    },
+                    (Cardinality::Many, _) => quote! {
+                        for __v in #ident.iter() {
+                            __query_vec.push((::std::borrow::Cow::Borrowed(#key_lit), ::waygate::param::to_scalar(#name_lit, __v)?));
                         }
-                    }
-                }
+                    },
+                };
+                self.body.extend(stmt);
             }
         }
 
@@ -321,21 +328,207 @@ impl MethodExpander {
                 __req = __req.timeout(::std::time::Duration::from_millis(#ms_lit as u64));
             });
         }
+        // #[timeout] 参数在调用时覆盖静态超时；Option<Duration> 为 None 时沿用
+        for p in &self.ctx.params {
+            if let ParamKind::Timeout = &p.kind {
+                let ident = &p.ident;
+                self.body.extend(match p.cardinality {
+                    Cardinality::Option => quote! {
+                        if let ::core::option::Option::Some(__t) = #ident {
+                            __req = __req.timeout(__t);
+                        }
+                    },
+                    _ => quote! { __req = __req.timeout(#ident); },
+                });
+            }
+        }
+        self
+    }
+
+    pub fn stage_apply_options(mut self) -> Self {
+        // #[options] 在全部静态与参数阶段之后应用，其请求头、查询参数与超时优先级最高
+        let policy = self.ctx.header_policy.to_token();
+        for p in &self.ctx.params {
+            if let ParamKind::Options = &p.kind {
+                let ident = &p.ident;
+                self.body.extend(match p.cardinality {
+                    Cardinality::Option => quote! {
+                        if let ::core::option::Option::Some(__o) = &#ident {
+                            __req = __o.apply(__req, #policy)?;
+                        }
+                    },
+                    _ => quote! { __req = #ident.apply(__req, #policy)?; },
+                });
+            }
+        }
         self
     }
 
     pub fn stage_send_and_denser(mut self) -> Self {
-        let ok_ty = &self.ctx.ok_ty;
+        // 声明 error_body 时，非 2xx 响应解码为 Error::Api（失败回退 Error::Http）；
+        // #[status(...)] 的状态码在解码时处理，not_found_as_none 放行 404
+        let send = match &self.ctx.error_body {
+            Some(_) if self.ctx.status_arms.is_empty() => {
+                let error = self.error_tokens();
+                let not_found = self.ctx.not_found_as_none.then(|| quote! {
+                    && __resp.status() != ::waygate::reqwest::StatusCode::NOT_FOUND
+                });
+                quote! {
+                    let __resp = self.core.execute(&__ROUTE, __req).await?;
+                    if !__resp.status().is_success() #not_found {
+                        return ::core::result::Result::Err(#error);
+                    }
+                }
+            }
+            _ => quote! {
+                let __resp = self.core.execute(&__ROUTE, __req).await?;
+            },
+        };
+        let out = match &self.ctx.shape {
+            ReturnShape::Body(ty) => {
+                let decode = self.decode_body(ty);
+                quote! {
+                    #send
+                    let __out = #decode;
+                }
+            }
+            // 在读取响应体前保留状态码、响应头与最终 URL
+            ReturnShape::Response(ty) => {
+                let decode = self.decode_body(ty);
+                quote! {
+                    let __start = ::std::time::Instant::now();
+                    #send
+                    let __status = __resp.status();
+                    let __headers = __resp.headers().clone();
+                    let __final_url = __resp.url().clone();
+                    let __body = #decode;
+                    let __out = ::waygate::Response {
+                        status: __status,
+                        headers: __headers,
+                        url: __final_url,
+                        elapsed: __start.elapsed(),
+                        body: __body,
+                    };
+                }
+            }
+            ReturnShape::Tuple { body, headers } => {
+                let decode = self.decode_body(body);
+                let values = headers.iter().map(|(name, ty)| {
+                    quote! { <#ty as ::waygate::response::FromResponseHeader>::from_headers(#name, &__headers)? }
+                });
+                quote! {
+                    #send
+                    let __headers = __resp.headers().clone();
+                    let __body = #decode;
+                    let __out = (__body, #(#values),*);
+                }
+            }
+        };
         self.body.extend(quote! {
-            let __resp = __req.send().await?;
-            let __out = __resp.json::<#ok_ty>().await?;
+            #out
             return ::core::result::Result::Ok(__out);
         });
         self
     }
 
+    // 非预期响应的错误：有 error_body 时按其解码，否则为 Error::Http
+    fn error_tokens(&self) -> TokenStream {
+        match &self.ctx.error_body {
+            Some(err_ty) => quote! { ::waygate::codec::error_from_response::<#err_ty>(__resp).await },
+            None => quote! { ::waygate::codec::unexpected_status(__resp).await },
+        }
+    }
+
+    // 解码响应体：#[status(...)] 按状态码选择枚举变体，not_found_as_none 时 404 为 None
+    fn decode_body(&self, ty: &syn::Type) -> TokenStream {
+        if !self.ctx.status_arms.is_empty() {
+            let arms = self.ctx.status_arms.iter().map(|arm| {
+                let codes = &arm.codes;
+                let variant = &arm.variant;
+                match &arm.payload {
+                    Some(payload) => {
+                        let value = self.decode_value(payload);
+                        quote! { #(#codes)|* => <#ty>::#variant(#value), }
+                    }
+                    None => quote! { #(#codes)|* => <#ty>::#variant, },
+                }
+            });
+            let error = self.error_tokens();
+            return quote! {
+                match __resp.status().as_u16() {
+                    #(#arms)*
+                    _ => return ::core::result::Result::Err(#error),
+                }
+            };
+        }
+        // `()` 不读取响应体，但与 `FromHead for ()` 一样要求 2xx
+        if self.ctx.method != Method::HEAD && matches!(ty, syn::Type::Tuple(t) if t.elems.is_empty()) {
+            let error = self.error_tokens();
+            return quote! {{
+                if !__resp.status().is_success() {
+                    return ::core::result::Result::Err(#error);
+                }
+            }};
+        }
+        let value = self.decode_value(ty);
+        if self.ctx.not_found_as_none {
+            return quote! {
+                if __resp.status() == ::waygate::reqwest::StatusCode::NOT_FOUND {
+                    ::core::option::Option::None
+                } else {
+                    #value
+                }
+            };
+        }
+        value
+    }
+
+    // 解码单个值：HEAD 按返回类型提取状态码/响应头，`()` 不读取响应体，其余按 JSON 解码
+    fn decode_value(&self, ty: &syn::Type) -> TokenStream {
+        if self.ctx.method == Method::HEAD {
+            return quote! { <#ty as ::waygate::codec::FromHead>::from_head(&__resp)? };
+        }
+        if matches!(ty, syn::Type::Tuple(t) if t.elems.is_empty()) {
+            return quote! { () };
+        }
+        quote! { __resp.json::<#ty>().await? }
+    }
+
+    pub fn stage_build_request(mut self) -> Self {
+        // prepare_<method>()：构建请求但不发送，经过拦截器并补齐客户端默认请求头与存储中的 Cookie
+        self.body.extend(quote! {
+            self.core.build_request(&__ROUTE, __req)
+        });
+        self
+    }
+
     pub fn finish(self) -> TokenStream {
         let body = self.body;
         quote! {{ #body }}
     }
-}
\ No newline at end of file
+}
+
+/// 将 `Bearer {token}` 形式的请求头值展开为拼接表达式，占位符取同名参数并按 serde 序列化
+fn header_template_tokens(value: &LitStr) -> TokenStream {
+    let template = value.value();
+    if template_placeholders(&template).is_empty() {
+        return quote! { #value };
+    }
+    let mut pieces = Vec::new();
+    let mut rest = template.as_str();
+    while let Some(start) = rest.find('{') {
+        let Some(len) = rest[start..].find('}') else { break; };
+        let literal = &rest[..start];
+        let name = &rest[start + 1..start + len];
+        let ident = syn::Ident::new(name, value.span());
+        pieces.push(quote! { __s.push_str(#literal); });
+        pieces.push(quote! { __s.push_str(&::waygate::param::to_scalar(#name, &#ident)?); });
+        rest = &rest[start + len + 1..];
+    }
+    pieces.push(quote! { __s.push_str(#rest); });
+    quote! {{
+        let mut __s = ::std::string::String::new();
+        #(#pieces)*
+        __s
+    }}
+}
@@ -0,0 +1,193 @@
+//! `openapi` 特性：为客户端生成 `openapi()`，返回 OpenAPI 3.1 文档
+//!
+//! 每个路由方法生成一段登记操作的语句；类型的 schema 经 `::waygate::openapi::SchemaProbe` 选择，
+//! 未实现 `JsonSchema` 的类型、引用方法泛型或 `Self` 的类型均为空 schema。
+
+use crate::expand::method::MethodCtx;
+use crate::http::method::Method;
+use crate::parser::{Cardinality, ParamKind, ReturnShape};
+use crate::util::{first_generic_arg, is_option_type, is_scalar_type};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::visit::Visit;
+use syn::visit_mut::VisitMut;
+use syn::{Attribute, Generics, Lifetime, Type};
+
+/// 客户端的 `openapi()`
+pub fn expand_openapi(trait_attrs: &[Attribute], title: &str, server: Option<&syn::LitStr>, operations: &[TokenStream]) -> TokenStream {
+    let description = doc_string(trait_attrs);
+    let server = server.map(|url| quote! { .server(#url) });
+    quote! {
+        /// 由 trait 声明生成的 OpenAPI 3.1 文档
+        pub fn openapi() -> ::waygate::openapi::Value {
+            #[allow(unused_imports)]
+            use ::waygate::openapi::{WithSchema as _, WithoutSchema as _};
+            let mut __doc = ::waygate::openapi::OpenApi::new(#title, ::core::env!("CARGO_PKG_VERSION"))
+                .description(#description)
+                #server;
+            #(#operations)*
+            __doc.into_value()
+        }
+    }
+}
+
+/// 登记单个路由方法的语句
+pub fn expand_operation(ctx: &MethodCtx, attrs: &[Attribute], generics: &Generics) -> TokenStream {
+    let doc = doc_string(attrs);
+    let cfgs = attrs.iter().filter(|a| a.path().is_ident("cfg"));
+    let route_info = &ctx.route_info;
+
+    let mut calls = Vec::new();
+    for p in &ctx.params {
+        let Some(ty) = &p.ty else { continue; };
+        let name_lit = syn::LitStr::new(&p.ident.to_string(), p.ident.span());
+        let required = matches!(p.cardinality, Cardinality::Single);
+        // Option<T> 参数描述 T；Vec<T>/切片保持数组
+        let value_ty = match p.cardinality {
+            Cardinality::Option => first_generic_arg(ty).unwrap_or(ty),
+            _ => ty,
+        };
+        let schema = schema_tokens(value_ty, generics);
+        calls.push(match &p.kind {
+            ParamKind::Path => quote! { .param(#name_lit, "path", true, #schema) },
+            ParamKind::Query { key: None } if !matches!(p.cardinality, Cardinality::Many) && !is_scalar_type(value_ty) => {
+                quote! { .query_object(#name_lit, #required, #schema) }
+            }
+            ParamKind::Query { key } => {
+                let key = key.clone().unwrap_or(name_lit);
+                quote! { .param(#key, "query", #required, #schema) }
+            }
+            ParamKind::Header { name } => quote! { .param(#name, "header", #required, #schema) },
+            ParamKind::Cookie { name } => {
+                let name = name.clone().unwrap_or(name_lit);
+                quote! { .param(#name, "cookie", #required, #schema) }
+            }
+            ParamKind::Json => {
+                let required = !matches!(p.cardinality, Cardinality::Option);
+                quote! { .json_body(#required, #schema) }
+            }
+            _ => continue,
+        });
+    }
+    for (k, v) in &ctx.static_query {
+        calls.push(quote! { .static_query(#k, #v) });
+    }
+
+    // 响应：#[status(...)] 逐个状态码；否则任意 2xx 视为成功
+    if !ctx.status_arms.is_empty() {
+        for arm in &ctx.status_arms {
+            let schema = match &arm.payload {
+                Some(payload) => {
+                    let schema = schema_tokens(payload, generics);
+                    quote! { ::core::option::Option::Some(#schema) }
+                }
+                None => quote! { ::core::option::Option::None },
+            };
+            for code in &arm.codes {
+                let code = code.to_string();
+                calls.push(quote! { .response(#code, #schema, ::std::vec::Vec::new()) });
+            }
+        }
+    } else {
+        let (body, headers) = match &ctx.shape {
+            ReturnShape::Body(ty) | ReturnShape::Response(ty) => (ty, &[][..]),
+            ReturnShape::Tuple { body, headers } => (body, &headers[..]),
+        };
+        let body = match ctx.not_found_as_none {
+            true => first_generic_arg(body).unwrap_or(body),
+            false => body,
+        };
+        let unit = matches!(body, Type::Tuple(t) if t.elems.is_empty());
+        let schema = if ctx.method == Method::HEAD || unit {
+            quote! { ::core::option::Option::None }
+        } else {
+            let schema = schema_tokens(body, generics);
+            quote! { ::core::option::Option::Some(#schema) }
+        };
+        let headers = headers.iter().map(|(name, ty)| {
+            let ty = if is_option_type(ty) { first_generic_arg(ty).unwrap_or(ty) } else { ty };
+            let schema = schema_tokens(ty, generics);
+            quote! { (#name, #schema) }
+        });
+        calls.push(quote! { .response("2XX", #schema, ::std::vec![#(#headers),*]) });
+        if ctx.not_found_as_none {
+            calls.push(quote! { .response("404", ::core::option::Option::None, ::std::vec::Vec::new()) });
+        }
+    }
+    if let Some(error_body) = &ctx.error_body {
+        let schema = schema_tokens(error_body, generics);
+        calls.push(quote! { .response("default", ::core::option::Option::Some(#schema), ::std::vec::Vec::new()) });
+    }
+
+    quote! {
+        #(#cfgs)*
+        {
+            const __ROUTE: ::waygate::RouteInfo = #route_info;
+            let __op = ::waygate::openapi::Operation::new().doc(#doc) #(#calls)*;
+            __doc.operation(&__ROUTE, __op);
+        }
+    }
+}
+
+/// 合并 `///` 文档注释，每行去掉一个前导空格
+fn doc_string(attrs: &[Attribute]) -> String {
+    let lines = attrs.iter().filter(|a| a.path().is_ident("doc")).filter_map(|a| match &a.meta {
+        syn::Meta::NameValue(nv) => match &nv.value {
+            syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(s), .. }) => Some(s.value()),
+            _ => None,
+        },
+        _ => None,
+    });
+    lines
+        .map(|line| line.strip_prefix(' ').map(str::to_string).unwrap_or(line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// 类型的 schema 表达式；生命周期统一替换为 'static，无法在 `openapi()` 中命名的类型为空 schema
+fn schema_tokens(ty: &Type, generics: &Generics) -> TokenStream {
+    let mut finder = Unnameable { generics, found: false };
+    finder.visit_type(ty);
+    if finder.found {
+        return quote! { ::waygate::openapi::Value::Object(::core::default::Default::default()) };
+    }
+    let mut ty = ty.clone();
+    StaticLifetimes.visit_type_mut(&mut ty);
+    quote! { (&::waygate::openapi::SchemaProbe::<#ty>::new()).schema(&mut __doc) }
+}
+
+/// 查找方法泛型参数、`Self` 与 `impl Trait`
+struct Unnameable<'a> {
+    generics: &'a Generics,
+    found: bool,
+}
+
+impl<'ast> Visit<'ast> for Unnameable<'_> {
+    fn visit_path(&mut self, path: &'ast syn::Path) {
+        if let Some(first) = path.segments.first()
+            && (first.ident == "Self" || self.generics.type_params().any(|p| p.ident == first.ident))
+        {
+            self.found = true;
+        }
+        syn::visit::visit_path(self, path);
+    }
+
+    fn visit_type_impl_trait(&mut self, _: &'ast syn::TypeImplTrait) {
+        self.found = true;
+    }
+}
+
+struct StaticLifetimes;
+
+impl VisitMut for StaticLifetimes {
+    fn visit_lifetime_mut(&mut self, lt: &mut Lifetime) {
+        *lt = Lifetime::new("'static", lt.span());
+    }
+
+    fn visit_type_reference_mut(&mut self, r: &mut syn::TypeReference) {
+        if r.lifetime.is_none() {
+            r.lifetime = Some(Lifetime::new("'static", proc_macro2::Span::call_site()));
+        }
+        syn::visit_mut::visit_type_reference_mut(self, r);
+    }
+}
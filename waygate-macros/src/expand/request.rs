@@ -2,10 +2,15 @@ use super::MacroCall;
 use crate::{
     parser::{
         RequestParser,
+        WaygateAttributes,
+        AttrValue,
+        HeaderPolicy,
+        ParamKind,
         MethodMetaParser,
         Parser
     },
-    expand::method::{MethodCtx, MethodExpander}
+    expand::method::{MethodCtx, MethodExpander},
+    util::{future_kind, is_param_attr, is_route_attr, FutureKind},
 };
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
@@ -23,33 +28,78 @@ impl super::Expander for WaygateExpander {
                 // 展开宏
                 let mut trait_item: ItemTrait = syn::parse2(item.clone())?;
 
+                // send/dyn 模式：先改写 trait 中的 async fn，其后的解析与 impl 沿用改写后的签名
+                if let Some(mode) = attributes.future_mode {
+                    super::future::rewrite_trait(&mut trait_item, mode)?;
+                }
+
                 let trait_ident = trait_item.ident.clone();
                 let client_ident = format_ident!("{}Client", trait_ident);
 
                 // 收集方法元信息（剥离前）
                 let methods = MethodMetaParser::parse(&trait_item)?;
+                check_prepare_names(&trait_item, &methods)?;
+                let assoc_impls = expand_assoc_items(&trait_item)?;
 
                 // 剥离自定义宏 避免“未知属性”错误
                 strip_custom_attrs_in_trait(&mut trait_item);
 
                 // 展开每个方法
                 let mut method_impls = Vec::new();
+                let mut prepares = Vec::new();
+                let mut routes = Vec::new();
+                let mut operations = Vec::new();
+                let mut builders = Vec::new();
                 for m in &methods {
-                    method_impls.push(expand_method_impl(&m)?);
+                    let items = expand_method_impl(m, &attributes, &trait_item)?;
+                    method_impls.push(items.method_impl);
+                    prepares.push(items.prepare);
+                    routes.push(items.route);
+                    operations.push(items.operation);
+                    if m.builder {
+                        builders.push(super::builder::expand_builder(m, &trait_item, &client_ident)?);
+                    }
                 }
 
+                // 具名主机：with_endpoint 与 new_default 均注册，运行时可用 with_host 覆盖
+                let host_pairs = attributes
+                    .hosts
+                    .iter()
+                    .map(|(name, value)| (name.clone(), value.to_token()))
+                    .collect::<Vec<_>>();
+                let host_stmts = host_pairs
+                    .iter()
+                    .map(|(name, value)| quote! { core.set_host(#name, #value)?; })
+                    .collect::<Vec<_>>();
+                let host_builder_stmts = host_pairs
+                    .iter()
+                    .map(|(name, value)| quote! { .host(#name, #value) })
+                    .collect::<Vec<_>>();
+
                 // 构造函数
                 let mut ctor_extra = quote! {
                     pub fn with_endpoint(endpoint: &str) -> ::waygate::Result<Self> {
-                        Ok(Self { core: ::waygate::HttpClient::with_endpoint(endpoint)? })
+                        #[allow(unused_mut)]
+                        let mut core = ::waygate::HttpClient::with_endpoint(endpoint)?;
+                        #(#host_stmts)*
+                        Ok(Self { core })
+                    }
+
+                    /// 覆盖具名主机的地址
+                    pub fn with_host(mut self, name: &str, endpoint: &str) -> ::waygate::Result<Self> {
+                        self.core.set_host(name, endpoint)?;
+                        Ok(self)
                     }
                 };
 
-                // 生成 .endpoint(...) 语句
-                let endpoint_stmt = if let Some(endpoint) = &attributes.endpoint {
-                    quote! { .endpoint(#endpoint) }
-                } else {
-                    quote! {}
+                // 生成 .endpoint(...) 语句；端点取自具名主机时保持关联，with_host/host 覆盖该主机即更新端点
+                let endpoint_stmt = match (&attributes.endpoint_host, &attributes.endpoint) {
+                    (Some(name), _) => quote! { .endpoint_host(#name) },
+                    (None, Some(endpoint)) => {
+                        let endpoint = endpoint.to_token();
+                        quote! { .endpoint(#endpoint) }
+                    }
+                    (None, None) => quote! {},
                 };
 
                 // 生成 .headers(vec![...])
@@ -57,11 +107,10 @@ impl super::Expander for WaygateExpander {
                     .headers
                     .into_iter()
                     .map(|(k, v)| {
-                        // "_" -> "-"
-                        let k = k.replace('_', "-").to_ascii_lowercase();
+                        // 名称已在解析时规范化
                         let k = syn::LitStr::new(&k, proc_macro2::Span::call_site());
-                        let v = syn::LitStr::new(&v, proc_macro2::Span::call_site());
-                        quote! { (::std::string::String::from(#k), ::std::string::String::from(#v)) }
+                        let v = v.to_token();
+                        quote! { (::std::string::String::from(#k), #v) }
                     })
                     .collect::<Vec<_>>();
 
@@ -71,6 +120,15 @@ impl super::Expander for WaygateExpander {
                     quote! { .headers(vec![ #(#headers_pairs),* ]) }
                 };
 
+                // 非默认的合并策略需同步到运行时配置
+                let header_policy_stmt = match attributes.header_policy {
+                    Some(policy) if policy != HeaderPolicy::Override => {
+                        let policy = policy.to_token();
+                        quote! { .header_policy(#policy) }
+                    }
+                    _ => quote! {},
+                };
+
                 // 生成 timeout 语句
                 let timeout_stmt = if let Some(timeout_ms) = attributes.timeout {
                     let timeout_duration =
@@ -100,23 +158,43 @@ impl super::Expander for WaygateExpander {
                     quote! {}
                 };
 
-                // 附加 new_default 构造函数
+                // 附加 builder 与 new_default 构造函数
                 ctor_extra = quote! {
                     #ctor_extra
+
+                    /// 以接口声明的默认配置创建构造器，可继续覆盖端点变量、主机与超时
+                    pub fn builder() -> ::waygate::ClientBuilder<Self> {
+                        let option = (|| -> ::waygate::Result<::waygate::HttpClientOptionBuilder> {
+                            let mut option = ::waygate::HttpClientOption::builder();
+                            option
+                                #endpoint_stmt
+                                #(#host_builder_stmts)*
+                                #headers_stmt
+                                #header_policy_stmt
+                                #timeout_stmt
+                                #connect_timeout_stmt
+                                #read_timeout_stmt;
+                            Ok(option)
+                        })();
+                        ::waygate::ClientBuilder::new(option)
+                    }
+
                     pub fn new_default() -> ::waygate::Result<Self> {
-                        let option = ::waygate::HttpClientOption::builder()
-                            #endpoint_stmt
-                            #headers_stmt
-                            #timeout_stmt
-                            #connect_timeout_stmt
-                            #read_timeout_stmt
-                            .build()
-                            .map_err(|e| ::waygate::Error::InvalidArgument(format!("Build HttpClientOption failed: {}", e)))?;
-
-                        Ok(Self { core: ::waygate::HttpClient::new(option)? })
+                        Self::builder().build()
                     }
                 };
 
+                // openapi 特性：字面量端点作为 servers
+                let openapi = if cfg!(feature = "openapi") {
+                    let server = match &attributes.endpoint {
+                        Some(AttrValue::Literal(url)) => Some(url),
+                        _ => None,
+                    };
+                    super::openapi::expand_openapi(&trait_item.attrs, &trait_ident.to_string(), server, &operations)
+                } else {
+                    TokenStream::new()
+                };
+
                 let expanded = quote! {
                     #trait_item
 
@@ -129,9 +207,25 @@ impl super::Expander for WaygateExpander {
                         #ctor_extra
                     }
 
+                    impl ::core::convert::From<::waygate::HttpClient> for #client_ident {
+                        fn from(core: ::waygate::HttpClient) -> Self { Self { core } }
+                    }
+
                     impl #trait_ident for #client_ident {
+                        #(#assoc_impls)*
                         #(#method_impls)*
                     }
+
+                    impl #client_ident {
+                        /// 全部路由方法的声明信息，按 trait 中的声明顺序排列
+                        pub const ROUTES: &'static [::waygate::RouteInfo] = &[#(#routes),*];
+
+                        #openapi
+
+                        #(#prepares)*
+                    }
+
+                    #(#builders)*
                 };
 
                 Ok(expanded)
@@ -144,34 +238,95 @@ impl super::Expander for WaygateExpander {
     }
 }
 
+/// 关联常量与关联类型：有默认值的由客户端继承，否则须以 `#[client(...)]` 给出客户端实现中的取值
+fn expand_assoc_items(trait_item: &ItemTrait) -> syn::Result<Vec<TokenStream>> {
+    let mut out = Vec::new();
+    for item in &trait_item.items {
+        match item {
+            TraitItem::Const(c) if c.default.is_none() => {
+                let value: syn::Expr = client_attr(&c.attrs, &c.ident, "const")?.parse_args()?;
+                let attrs = c.attrs.iter().filter(|a| !a.path().is_ident("client"));
+                let (ident, ty) = (&c.ident, &c.ty);
+                out.push(quote! { #(#attrs)* const #ident: #ty = #value; });
+            }
+            TraitItem::Type(t) if t.default.is_none() => {
+                let value: syn::Type = client_attr(&t.attrs, &t.ident, "type")?.parse_args()?;
+                let attrs = t.attrs.iter().filter(|a| !a.path().is_ident("client"));
+                let ident = &t.ident;
+                let (impl_generics, _, where_clause) = t.generics.split_for_impl();
+                out.push(quote! { #(#attrs)* type #ident #impl_generics = #value #where_clause; });
+            }
+            _ => {}
+        }
+    }
+    Ok(out)
+}
+
+fn client_attr<'a>(attrs: &'a [syn::Attribute], ident: &syn::Ident, kind: &str) -> syn::Result<&'a syn::Attribute> {
+    attrs.iter().find(|a| a.path().is_ident("client")).ok_or_else(|| {
+        syn::Error::new(
+            ident.span(),
+            format!(
+                "associated {} `{}` has no default; declare the client's value with #[client(...)]",
+                kind, ident
+            ),
+        )
+    })
+}
+
 fn strip_custom_attrs_in_trait(trait_item: &mut ItemTrait) {
     for item in &mut trait_item.items {
+        // 关联常量/类型：去掉 #[client(...)]
+        match item {
+            TraitItem::Const(c) => c.attrs.retain(|a| !a.path().is_ident("client")),
+            TraitItem::Type(t) => t.attrs.retain(|a| !a.path().is_ident("client")),
+            _ => {}
+        }
         if let TraitItem::Fn(m) = item {
-            // 方法级：去掉 get/post/put/delete/patch
-            m.attrs.retain(|a| {
-                let Some(id) = a.path().get_ident() else {
-                    return true;
-                };
-                let n = id.to_string();
-                !matches!(n.as_str(), "get" | "post" | "put" | "delete" | "patch")
-            });
-            // 参数级：去掉 path/query/json/header
+            // 方法级：去掉全部路由属性（含 head/options/trace 与扩展方法 http）及 response_header/status
+            m.attrs.retain(|a| !is_route_attr(a));
+            // 参数级：去掉 path/query/json/header/headers/cookie/host_var/timeout/options
             for input in &mut m.sig.inputs {
                 if let FnArg::Typed(pt) = input {
-                    pt.attrs.retain(|a| {
-                        let Some(id) = a.path().get_ident() else {
-                            return true;
-                        };
-                        let n = id.to_string();
-                        !matches!(n.as_str(), "path" | "query" | "json" | "header")
-                    });
+                    pt.attrs.retain(|a| !is_param_attr(a));
                 }
             }
         }
     }
 }
 
-fn expand_method_impl(meta: &crate::parser::MethodMeta) -> syn::Result<TokenStream> {
+/// 生成的 `prepare_<method>()` 为客户端的固有方法，调用时优先于同名 trait 方法，因此不允许重名
+fn check_prepare_names(trait_item: &ItemTrait, methods: &[crate::parser::MethodMeta]) -> syn::Result<()> {
+    for item in &trait_item.items {
+        let TraitItem::Fn(f) = item else { continue; };
+        let name = f.sig.ident.to_string();
+        let Some(route) = name.strip_prefix("prepare_") else { continue; };
+        if let Some(m) = methods.iter().find(|m| m.sig_ident == route) {
+            return Err(syn::Error::new(
+                f.sig.ident.span(),
+                format!(
+                    "`{}` clashes with the generated dry-run method for `{}`; rename this method",
+                    name, m.sig_ident
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// 单个路由方法展开得到的各部分
+struct MethodItems {
+    method_impl: TokenStream, // trait 方法的实现
+    prepare: TokenStream,     // 构建同一请求但不发送的 `prepare_<method>()`
+    route: TokenStream,       // `ROUTES` 中的元素
+    operation: TokenStream,   // `openapi()` 中登记操作的语句，未启用 openapi 特性时为空
+}
+
+fn expand_method_impl(
+    meta: &crate::parser::MethodMeta,
+    attributes: &WaygateAttributes,
+    trait_item: &ItemTrait,
+) -> syn::Result<MethodItems> {
     // 将 MethodMeta 映射到 MethodCtx（补齐默认值/校验）
     let route = meta.route.clone();
     let method = route.method.ok_or_else(|| syn::Error::new(meta.sig_ident.span(), "缺少 HTTP 方法"))?;
@@ -179,18 +334,77 @@ fn expand_method_impl(meta: &crate::parser::MethodMeta) -> syn::Result<TokenStre
 
     let sig_ident = meta.sig_ident.clone(); // 方法签名
 
+    // 方法级 host 必须在接口级 hosts(...) 中声明
+    if let Some(host) = &route.host
+        && !attributes.hosts.iter().any(|(name, _)| *name == host.value())
+    {
+        return Err(syn::Error::new(
+            host.span(),
+            format!("unknown host `{}`; declare it in #[request(hosts(...))]", host.value()),
+        ));
+    }
+
+    // #[host_var] 仅作用于接口级端点模板，且占位符须存在于字面量端点中
+    for p in &meta.params {
+        let ParamKind::HostVar { name } = &p.kind else { continue; };
+        if route.endpoint.is_some() || route.host.is_some() {
+            return Err(syn::Error::new(
+                p.ident.span(),
+                "#[host_var] cannot be combined with a method-level `endpoint` or `host`",
+            ));
+        }
+        if let Some(AttrValue::Literal(endpoint)) = &attributes.endpoint {
+            let name = name.as_ref().map(|n| n.value()).unwrap_or_else(|| p.ident.to_string());
+            if !crate::util::template_placeholders(&endpoint.value()).contains(&name.as_str()) {
+                return Err(syn::Error::new(
+                    p.ident.span(),
+                    format!("endpoint `{}` has no placeholder `{{{}}}`", endpoint.value(), name),
+                ));
+            }
+        }
+    }
+
+    // not_found_as_none 要求响应体为 Option<T>，且不能与 #[status(...)] 同时使用
+    if route.not_found_as_none {
+        let body = meta.shape.body();
+        if !crate::util::is_option_type(body) {
+            return Err(syn::Error::new_spanned(body, "`not_found_as_none` requires the response body type to be Option<T>"));
+        }
+        if let Some(arm) = meta.status_arms.first() {
+            return Err(syn::Error::new(arm.variant.span(), "`not_found_as_none` cannot be combined with #[status(...)]"));
+        }
+    }
+
+    // 方法级策略优先，其次接口级，默认覆盖
+    let header_policy = route.header_policy.or(attributes.header_policy).unwrap_or_default();
+
+    let route_headers = header_policy.dedup(route.headers.clone());
+    let route_info = route_info_tokens(meta, &method, &path, &route_headers);
+
     let ctx = MethodCtx {
         sig_ident,
-        ok_ty: meta.ok_ty.clone(),
+        shape: meta.shape.clone(),
+        error_body: route.error_body.clone().or_else(|| attributes.error_body.clone()),
+        not_found_as_none: route.not_found_as_none,
+        status_arms: meta.status_arms.clone(),
         method,
-        endpoint: None, // trait 级别的可传入
+        endpoint: route.endpoint.clone(),
+        host: route.host.as_ref().map(|h| h.value()),
         path,
-        route_headers: route.headers.clone(),
+        route_headers,
+        header_policy,
+        static_query: merge_static_query(&attributes.query, &route.query),
         timeout_ms: route.timeout,
         params: meta.params.clone(), // 统一参数模型
+        route_info: route_info.clone(),
+    };
+    let operation = if cfg!(feature = "openapi") {
+        super::openapi::expand_operation(&ctx, &meta.attrs, &meta.sig.generics)
+    } else {
+        TokenStream::new()
     };
 
-    let body = MethodExpander::new(ctx)
+    let request = MethodExpander::new(ctx)
         .validate()?
         .stage_init()
         .stage_replace_path_params()
@@ -198,29 +412,117 @@ fn expand_method_impl(meta: &crate::parser::MethodMeta) -> syn::Result<TokenStre
         .stage_request_builder()
         .stage_apply_static_headers()
         .stage_apply_param_headers()
+        .stage_apply_cookies()
         .stage_apply_query()
         .stage_apply_json()
         .stage_apply_timeout()
-        .stage_send_and_denser()
-        .finish();
+        .stage_apply_options();
+    let prepare_body = request.clone().stage_build_request().finish();
+    let body = request.stage_send_and_denser().finish();
 
-    // 用参数元信息组装 impl 方法的形参列表（跳过 self 等无类型参数）
-    let mut impl_params = Vec::new();
-    for p in &meta.params {
-        if let Some(ty) = &p.ty {
-            let id = &p.ident;
-            impl_params.push(quote! { #id: #ty });
+    // 沿用声明的签名（泛型、生命周期、where 子句、receiver 与返回类型），仅去掉宏自身的属性
+    let mut sig = meta.sig.clone();
+    for input in &mut sig.inputs {
+        if let FnArg::Typed(pt) = input {
+            pt.attrs.retain(|a| !is_param_attr(a));
         }
     }
-
-    let ident = &meta.sig_ident;
+    let attrs = meta.attrs.iter().filter(|a| !is_route_attr(a));
     let ok_ty = &meta.ok_ty;
 
-    // 返回完整的方法定义
-    Ok(quote! {
-        async fn #ident(&self, #(#impl_params),*) -> ::waygate::Result<#ok_ty> {
-            #body
-        }
+    // waygate::Error 经 From 转换为声明的错误类型；非 async 签名返回 async move 块（或其 Box::pin）
+    let call = quote! {
+        let __result: ::waygate::Result<#ok_ty> = async #body.await;
+        __result.map_err(::core::convert::From::from)
+    };
+    let block = match future_kind(&sig) {
+        FutureKind::Async => quote! {{ #call }},
+        FutureKind::Impl => quote! {{ async move { #call } }},
+        FutureKind::Boxed => quote! {{ ::std::boxed::Box::pin(async move { #call }) }},
+    };
+
+    // prepare_<method>()：同步构建请求，签名沿用参数与泛型，返回 reqwest::Request
+    let mut prepare_sig = sig.clone();
+    prepare_sig.asyncness = None;
+    prepare_sig.ident = format_ident!("prepare_{}", meta.sig_ident);
+    prepare_sig.output = syn::parse_quote!(-> ::waygate::Result<::waygate::reqwest::Request>);
+    let cfgs = meta.attrs.iter().filter(|a| a.path().is_ident("cfg")).collect::<Vec<_>>();
+    let doc = format!("构建 [`{}::{}`] 将发送的请求但不发送，用于调试、签名与审计", trait_item.ident, meta.sig_ident);
+    let vis = &trait_item.vis;
+
+    Ok(MethodItems {
+        method_impl: quote! {
+            #(#attrs)*
+            #sig #block
+        },
+        prepare: quote! {
+            #(#cfgs)*
+            #[doc = #doc]
+            #vis #prepare_sig #prepare_body
+        },
+        route: quote! {
+            #(#cfgs)*
+            #route_info
+        },
+        operation,
     })
+}
+
+/// 方法的 `::waygate::RouteInfo` 字面量；参数不含 receiver
+fn route_info_tokens(
+    meta: &crate::parser::MethodMeta,
+    method: &crate::http::method::Method,
+    path: &str,
+    headers: &[(String, syn::LitStr)],
+) -> TokenStream {
+    let name = meta.sig_ident.to_string();
+    let method = method.as_str();
+    let params = meta.params.iter().filter(|p| p.ty.is_some()).map(|p| {
+        let name = p.ident.to_string();
+        let (kind, key) = match &p.kind {
+            ParamKind::Path => (quote! { Path }, None),
+            ParamKind::Query { key } => (quote! { Query }, key.as_ref()),
+            ParamKind::Json => (quote! { Json }, None),
+            ParamKind::Header { name } => (quote! { Header }, Some(name)),
+            ParamKind::Headers => (quote! { Headers }, None),
+            ParamKind::Cookie { name } => (quote! { Cookie }, name.as_ref()),
+            ParamKind::HostVar { name } => (quote! { HostVar }, name.as_ref()),
+            ParamKind::Timeout => (quote! { Timeout }, None),
+            ParamKind::Options => (quote! { Options }, None),
+            ParamKind::Other => (quote! { Other }, None),
+        };
+        let key = match key {
+            Some(key) => quote! { ::core::option::Option::Some(#key) },
+            None => quote! { ::core::option::Option::None },
+        };
+        quote! {
+            ::waygate::route::ParamInfo { name: #name, kind: ::waygate::route::ParamKind::#kind, key: #key }
+        }
+    });
+    let headers = headers.iter().map(|(k, v)| quote! { (#k, #v) });
+    let timeout = match meta.route.timeout {
+        Some(ms) => quote! { ::core::option::Option::Some(::std::time::Duration::from_millis(#ms)) },
+        None => quote! { ::core::option::Option::None },
+    };
+    quote! {
+        ::waygate::RouteInfo {
+            name: #name,
+            method: #method,
+            path: #path,
+            params: &[#(#params),*],
+            headers: &[#(#headers),*],
+            timeout: #timeout,
+        }
+    }
+}
 
+/// 合并接口级与方法级静态查询参数：方法级同名键覆盖接口级
+fn merge_static_query(trait_query: &[(String, String)], route_query: &[(String, String)]) -> Vec<(String, String)> {
+    let mut merged: Vec<(String, String)> = trait_query
+        .iter()
+        .filter(|(k, _)| !route_query.iter().any(|(rk, _)| rk == k))
+        .cloned()
+        .collect();
+    merged.extend(route_query.iter().cloned());
+    merged
 }
@@ -2,7 +2,8 @@ use std::str::FromStr;
 use quote::quote;
 
 /// HTTP 请求方法枚举
-#[derive(Debug, Clone, Copy)]
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Method {
     GET,
     POST,
@@ -12,9 +13,24 @@ pub enum Method {
     DELETE,
     PATCH,
     TRACE,
+    Extension(String), // 扩展方法，如 WebDAV 的 PROPFIND，仅能通过 #[http(method = "...")] 声明
 }
 
 impl Method {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Method::GET => "GET",
+            Method::POST => "POST",
+            Method::PUT => "PUT",
+            Method::HEAD => "HEAD",
+            Method::OPTIONS => "OPTIONS",
+            Method::DELETE => "DELETE",
+            Method::PATCH => "PATCH",
+            Method::TRACE => "TRACE",
+            Method::Extension(name) => name,
+        }
+    }
+
     pub fn to_token(&self) -> proc_macro2::TokenStream {
         match self {
             Method::GET => quote! { ::waygate::reqwest::Method::GET },
@@ -25,6 +41,11 @@ impl Method {
             Method::DELETE => quote! { ::waygate::reqwest::Method::DELETE },
             Method::PATCH => quote! { ::waygate::reqwest::Method::PATCH },
             Method::TRACE => quote! { ::waygate::reqwest::Method::TRACE },
+            // 名称已在编译期校验，运行时仍以错误而非 panic 处理
+            Method::Extension(name) => quote! {
+                ::waygate::reqwest::Method::from_bytes(#name.as_bytes())
+                    .map_err(|e| ::waygate::Error::InvalidArgument(e.to_string()))?
+            },
         }
     }
 }
@@ -42,6 +63,8 @@ impl FromStr for Method {
             "DELETE" => Ok(Method::DELETE),
             "PATCH" => Ok(Method::PATCH),
             "TRACE" => Ok(Method::TRACE),
+            // 扩展方法区分大小写，按原样保留；名称须为 RFC 9110 token
+            _ if !s.is_empty() && s.chars().all(is_token_char) => Ok(Method::Extension(s.to_string())),
             _ => Err(format!("Unsupported HTTP method: {}", s)),
         }
     }
@@ -51,4 +74,7 @@ impl From<String> for Method {
     fn from(value: String) -> Self {
         Method::from_str(&value).unwrap_or(Method::GET)
     }
-}
\ No newline at end of file
+}
+fn is_token_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c)
+}
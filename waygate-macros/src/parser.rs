@@ -2,7 +2,7 @@
 
 mod request;
 
-pub use request::{RequestParser, MethodMetaParser, MethodMeta, ParamKind, ParamMeta, Cardinality};
+pub use request::{RequestParser, MethodMetaParser, MethodMeta, ParamKind, ParamMeta, Cardinality, WaygateAttributes, AttrValue, HeaderPolicy, ReturnShape, StatusArm, FutureMode};
 
 /// 解析器 trait
 pub trait Parser<I> {
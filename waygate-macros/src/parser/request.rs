@@ -1,11 +1,27 @@
-use crate::{http::method::Method, util::extract_ok_type};
+use crate::{http::method::Method, util::{extract_ok_type, extract_response_type, first_generic_arg, is_non_scalar_type}};
 use derive_builder::Builder;
 use proc_macro2::TokenStream;
 use std::str::FromStr;
-use syn::{Attribute, FnArg, ItemTrait, LitInt, LitStr, Pat, TraitItem, parse::Parser, punctuated::Punctuated, spanned::Spanned, token::Comma, Type};
+use syn::{Attribute, FnArg, ItemTrait, LitInt, LitStr, Meta, Pat, TraitItem, ext::IdentExt, parse::Parser, punctuated::Punctuated, spanned::Spanned, token::Comma, Type};
 
 const DEFAULT_USER_AGENT_KEY: &str = "user-agent";
 const DEFAULT_USER_AGENT_VALUE: &str = concat!("waygate-client/", env!("CARGO_PKG_VERSION"));
+const DEFAULT_ENDPOINT: &str = "http://localhost";
+
+/// 接口级 `#[request(...)]` 支持的键
+const TRAIT_KEYS: &[&str] = &[
+    "endpoint", "hosts", "headers", "header_policy", "error_body", "send", "dyn", "query", "timeout",
+    "connect_timeout", "read_timeout",
+];
+/// 方法级 `#[get(...)]` 等支持的键
+const ROUTE_KEYS: &[&str] = &[
+    "endpoint", "host", "path", "headers", "header_policy", "error_body", "not_found_as_none", "query", "timeout",
+];
+/// `#[http(...)]` 额外支持 `method`
+const HTTP_ROUTE_KEYS: &[&str] = &[
+    "method", "endpoint", "host", "path", "headers", "header_policy", "error_body", "not_found_as_none", "query",
+    "timeout",
+];
 
 // 接口级宏上的属性解析器
 pub struct RequestParser;
@@ -18,11 +34,39 @@ pub struct MethodMetaParser;
 
 pub struct ParamMetaParser;
 
+/// 属性值：字符串字面量或构造时读取的环境变量 `env("NAME", default = "...")`
+#[derive(Debug, Clone)]
+pub enum AttrValue {
+    Literal(LitStr),
+    Env { name: LitStr, default: Option<LitStr> },
+}
+
+/// 同名请求头的合并策略，对应运行时 `waygate::HeaderPolicy`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HeaderPolicy {
+    #[default]
+    Override,
+    Append,
+}
+
+/// 生成 trait 方法的 Future 形态：`#[request(send)]` 或 `#[request(dyn)]`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FutureMode {
+    Send, // fn ... -> impl Future<Output = R> + Send
+    Dyn,  // fn ... -> Pin<Box<dyn Future<Output = R> + Send + '_>>，trait 可作为 dyn 使用
+}
+
 /// 接口级解析属性
 #[derive(Debug)]
 pub struct WaygateAttributes {
-    pub endpoint: Option<String>,       // 基础端点 URL
-    pub headers: Vec<(String, String)>, // 额外请求头
+    pub endpoint: Option<AttrValue>,       // 基础端点 URL
+    pub hosts: Vec<(String, AttrValue)>,   // 具名主机
+    pub endpoint_host: Option<String>,     // 未声明 endpoint 时端点取自的具名主机
+    pub headers: Vec<(String, AttrValue)>, // 额外请求头，名称已规范化
+    pub header_policy: Option<HeaderPolicy>, // 同名请求头合并策略
+    pub error_body: Option<Type>,       // 非 2xx 响应体的解码类型
+    pub future_mode: Option<FutureMode>, // 方法返回的 Future 形态，默认保持 async fn
+    pub query: Vec<(String, String)>,   // 静态查询参数
     pub timeout: Option<u64>,           // 请求超时，单位毫秒
     pub connect_timeout: Option<u64>,   // 连接超时，单位毫秒
     pub read_timeout: Option<u64>,      // 读取超时，单位毫秒
@@ -31,8 +75,14 @@ pub struct WaygateAttributes {
 #[derive(Debug, Clone)]
 pub struct RequestRouteAttributes {
     pub method: Option<Method>,         // HTTP 方法
+    pub endpoint: Option<String>,       // 方法级端点，覆盖接口级
+    pub host: Option<LitStr>,           // 方法级具名主机
     pub path: Option<String>,           // 请求路径
-    pub headers: Vec<(String, String)>, // 额外请求头
+    pub headers: Vec<(String, LitStr)>, // 额外请求头，名称已规范化，值可含 {param} 模板
+    pub header_policy: Option<HeaderPolicy>, // 同名请求头合并策略，覆盖接口级
+    pub error_body: Option<Type>,       // 非 2xx 响应体的解码类型，覆盖接口级
+    pub not_found_as_none: bool,        // 404 返回 Ok(None)，返回类型须为 Option<T>
+    pub query: Vec<(String, String)>,   // 静态查询参数
     pub timeout: Option<u64>,           // 请求超时，单位毫秒
 }
 
@@ -40,8 +90,54 @@ pub struct RequestRouteAttributes {
 pub struct MethodMeta {
     pub sig_ident: syn::Ident,
     pub ok_ty: TokenStream,
+    pub sig: syn::Signature,     // 声明的完整签名（含自定义错误类型或别名），impl 中原样沿用
+    pub attrs: Vec<Attribute>,   // 方法上的属性（#[cfg]、文档等），impl 中保留非路由属性
+    pub shape: ReturnShape,
+    pub status_arms: Vec<StatusArm>,
     pub params: Vec<ParamMeta>,
     pub route: RequestRouteAttributes,
+    pub builder: bool,           // #[builder]：生成链式请求构造器
+}
+
+/// `#[status(...)]` 中的一条映射：`200 | 201 => Found(T)` 或 `404 => Missing`
+#[derive(Debug, Clone)]
+pub struct StatusArm {
+    pub codes: Vec<u16>,
+    pub variant: syn::Ident,
+    pub payload: Option<Type>, // 响应体解码类型，单元变体为 None
+}
+
+impl syn::parse::Parse for StatusArm {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let codes = Punctuated::<LitInt, syn::Token![|]>::parse_separated_nonempty(input)?
+            .into_iter()
+            .map(|lit| {
+                let code: u16 = lit.base10_parse()?;
+                if !(100..=999).contains(&code) {
+                    return Err(syn::Error::new(lit.span(), "status code must be between 100 and 999"));
+                }
+                Ok(code)
+            })
+            .collect::<syn::Result<Vec<_>>>()?;
+        input.parse::<syn::Token![=>]>()?;
+        let variant: syn::Ident = input.parse()?;
+        let payload = if input.peek(syn::token::Paren) {
+            let content;
+            syn::parenthesized!(content in input);
+            Some(content.parse::<Type>()?)
+        } else {
+            None
+        };
+        Ok(StatusArm { codes, variant, payload })
+    }
+}
+
+/// 返回值形态
+#[derive(Debug, Clone)]
+pub enum ReturnShape {
+    Body(Type),     // 仅响应体
+    Response(Type), // waygate::Response<T>，附带状态码、响应头等元信息
+    Tuple { body: Type, headers: Vec<(LitStr, Type)> }, // (T, H1, ...)，配合 #[response_header("...")]
 }
 
 /// 参数标注类型
@@ -50,7 +146,12 @@ pub enum ParamKind {
     Path,
     Query { key: Option<LitStr> },
     Json,
-    Header { name: Option<LitStr> },
+    Header { name: LitStr },
+    Headers,
+    Cookie { name: Option<LitStr> },
+    HostVar { name: Option<LitStr> },
+    Timeout, // #[timeout] t: Duration / Option<Duration>
+    Options, // #[options] opts: waygate::RequestOptions
     Other,
 }
 
@@ -78,12 +179,26 @@ pub enum Cardinality {
 struct AttributeProperties {
     #[builder(default = "Some(Method::GET)")]
     method: Option<Method>, // HTTP 方法
-    #[builder(default = "Some(String::from(\"http://localhost\"))")]
-    endpoint: Option<String>, // 基础端点 URL
+    #[builder(default = "None")]
+    endpoint: Option<AttrValue>, // 基础端点 URL
+    #[builder(default)]
+    hosts: Vec<(String, AttrValue)>, // 具名主机
+    #[builder(default = "None")]
+    host: Option<LitStr>, // 方法级具名主机
     #[builder(default = "None")]
     path: Option<String>, // 请求路径
     #[builder(default)]
-    headers: Vec<(String, String)>, // 额外请求头
+    headers: Vec<(String, AttrValue)>, // 额外请求头
+    #[builder(default = "None")]
+    header_policy: Option<HeaderPolicy>, // 同名请求头合并策略
+    #[builder(default = "None")]
+    error_body: Option<Type>, // 非 2xx 响应体的解码类型
+    #[builder(default)]
+    not_found_as_none: bool, // 404 返回 Ok(None)
+    #[builder(default = "None")]
+    future_mode: Option<FutureMode>, // 方法返回的 Future 形态
+    #[builder(default)]
+    query: Vec<(String, String)>, // 静态查询参数
     #[builder(default = "None")]
     timeout: Option<u64>, // 请求超时，单位毫秒
     #[builder(default = "None")]
@@ -92,6 +207,88 @@ struct AttributeProperties {
     read_timeout: Option<u64>, // 读取超时，单位毫秒
 }
 
+impl AttrValue {
+    /// 生成求值为 `String` 的表达式；环境变量缺失时经 `?` 返回 `Error::InvalidArgument`
+    pub fn to_token(&self) -> TokenStream {
+        match self {
+            AttrValue::Literal(lit) => quote::quote! { ::std::string::String::from(#lit) },
+            AttrValue::Env { name, default: Some(default) } => quote::quote! {
+                ::waygate::config::env_var(#name, ::core::option::Option::Some(#default))?
+            },
+            AttrValue::Env { name, default: None } => quote::quote! {
+                ::waygate::config::env_var(#name, ::core::option::Option::None)?
+            },
+        }
+    }
+
+    /// 方法级属性仅支持字面量
+    fn into_literal(self) -> syn::Result<LitStr> {
+        match self {
+            AttrValue::Literal(lit) => Ok(lit),
+            AttrValue::Env { name, .. } => Err(syn::Error::new(
+                name.span(),
+                "env(...) is only supported in the trait-level #[request(...)] attribute",
+            )),
+        }
+    }
+}
+
+impl HeaderPolicy {
+    pub fn to_token(self) -> TokenStream {
+        match self {
+            HeaderPolicy::Override => quote::quote! { ::waygate::HeaderPolicy::Override },
+            HeaderPolicy::Append => quote::quote! { ::waygate::HeaderPolicy::Append },
+        }
+    }
+
+    /// 合并同一 `headers(...)` 中的重复键：`Override` 保留最后一个值（位置不变），`Append` 全部保留
+    pub fn dedup<V>(self, headers: Vec<(String, V)>) -> Vec<(String, V)> {
+        if self == HeaderPolicy::Append {
+            return headers;
+        }
+        let mut out: Vec<(String, V)> = Vec::with_capacity(headers.len());
+        for (k, v) in headers {
+            match out.iter_mut().find(|(existing, _)| *existing == k) {
+                Some(slot) => slot.1 = v,
+                None => out.push((k, v)),
+            }
+        }
+        out
+    }
+}
+
+impl syn::parse::Parse for AttrValue {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        if input.peek(LitStr) {
+            return Ok(AttrValue::Literal(input.parse()?));
+        }
+        let ident: syn::Ident = input.parse()?;
+        if ident != "env" {
+            return Err(syn::Error::new(
+                ident.span(),
+                "expected a string literal or env(\"NAME\", default = \"...\")",
+            ));
+        }
+        let content;
+        syn::parenthesized!(content in input);
+        let name: LitStr = content.parse()?;
+        let mut default = None;
+        if content.parse::<Option<syn::Token![,]>>()?.is_some() && !content.is_empty() {
+            let key: syn::Ident = content.parse()?;
+            if key != "default" {
+                return Err(syn::Error::new(key.span(), "expected `default = \"...\"`"));
+            }
+            content.parse::<syn::Token![=]>()?;
+            default = Some(content.parse::<LitStr>()?);
+            content.parse::<Option<syn::Token![,]>>()?;
+        }
+        if !content.is_empty() {
+            return Err(content.error("unexpected tokens in env(...)"));
+        }
+        Ok(AttrValue::Env { name, default })
+    }
+}
+
 impl RequestRouteAttributes {
     fn set_path_if_none(&mut self, path: String) {
         if self.path.is_none() {
@@ -112,7 +309,7 @@ impl crate::parser::Parser<TokenStream> for RequestParser {
     fn parse(input: &TokenStream) -> syn::Result<Self::Output> {
         let mut builder = AttributeProperties::builder();
 
-        let parser = get_parser(&mut builder);
+        let parser = get_parser(&mut builder, TRAIT_KEYS);
 
         parser.parse2(input.clone())?;
 
@@ -122,9 +319,32 @@ impl crate::parser::Parser<TokenStream> for RequestParser {
                 format!("Failed to build WaygateAttributes: {}", e)
             )
         })?;
+        // 未声明 endpoint 时，使用第一个具名主机，否则回退到 http://localhost
+        let endpoint_host = match &properties.endpoint {
+            None => properties.hosts.first().map(|(name, _)| name.clone()),
+            Some(_) => None,
+        };
+        let endpoint = properties
+            .endpoint
+            .or_else(|| properties.hosts.first().map(|(_, v)| v.clone()))
+            .unwrap_or_else(|| AttrValue::Literal(LitStr::new(DEFAULT_ENDPOINT, proc_macro2::Span::call_site())));
+        let mut headers = properties.header_policy.unwrap_or_default().dedup(properties.headers);
+        // 声明了 headers(...) 但未设置 User-Agent 时补充默认值（方法级沿用客户端默认头，不再注入）
+        if !headers.is_empty() && !headers.iter().any(|(k, _)| k == DEFAULT_USER_AGENT_KEY) {
+            headers.push((
+                DEFAULT_USER_AGENT_KEY.to_string(),
+                AttrValue::Literal(LitStr::new(DEFAULT_USER_AGENT_VALUE, proc_macro2::Span::call_site())),
+            ));
+        }
         Ok(WaygateAttributes {
-            endpoint: properties.endpoint,
-            headers: properties.headers,
+            endpoint: Some(endpoint),
+            hosts: properties.hosts,
+            endpoint_host,
+            headers,
+            header_policy: properties.header_policy,
+            error_body: properties.error_body,
+            future_mode: properties.future_mode,
+            query: properties.query,
             timeout: properties.timeout,
             connect_timeout: properties.connect_timeout,
             read_timeout: properties.read_timeout,
@@ -137,9 +357,21 @@ impl crate::parser::Parser<Vec<Attribute>> for RequestRouteParser {
 
     fn parse(attrs: &Vec<Attribute>) -> syn::Result<Self::Output> {
         let mut builder = AttributeProperties::builder();
+        let mut routed = false; // 是否声明了路由属性
         for attr in attrs {
             if let Some(ident) = attr.path().get_ident() {
                 let name = ident.to_string().to_uppercase(); // 方法级注解名称
+                if name == "HTTP" {
+                    check_single_route(attr, routed)?;
+                    routed = true;
+                    // #[http(method = "PROPFIND", path = "...")]：扩展方法须显式声明 method
+                    builder.method(None);
+                    attr.parse_args_with(get_parser(&mut builder, HTTP_ROUTE_KEYS))?;
+                    if matches!(builder.method, Some(None)) {
+                        return Err(syn::Error::new(attr.span(), "#[http(...)] requires `method = \"...\"`"));
+                    }
+                    continue;
+                }
                 if !matches!(
                     name.as_str(),
                     "GET" | "POST" | "PUT" | "DELETE" | "PATCH" | "HEAD" | "OPTIONS" | "TRACE"
@@ -147,25 +379,49 @@ impl crate::parser::Parser<Vec<Attribute>> for RequestRouteParser {
                     continue;
                 }
 
+                check_single_route(attr, routed)?;
+                routed = true;
                 builder.method(Some(
                     Method::from_str(&name).map_err(|e| syn::Error::new(attr.span(), e))?,
                 ));
 
-                let parser = get_parser(&mut builder);
-
-                attr.parse_args_with(parser)?
+                // 无参数的 #[get] 沿用默认路径 `/{方法名}`
+                if !matches!(attr.meta, Meta::Path(_)) {
+                    attr.parse_args_with(get_parser(&mut builder, ROUTE_KEYS))?;
+                }
             }
         }
+        if !routed {
+            return Ok(None);
+        }
         let properties = builder.build().map_err(|e| {
             syn::Error::new(
                 proc_macro2::Span::call_site(),
                 format!("Failed to build WaygateRouteAttributes: {}", e),
             )
         })?;
+        let headers = properties
+            .headers
+            .into_iter()
+            .map(|(k, v)| v.into_literal().map(|v| (k, v)))
+            .collect::<syn::Result<Vec<_>>>()?;
+        let endpoint = properties
+            .endpoint
+            .map(|v| v.into_literal().map(|lit| lit.value()))
+            .transpose()?;
+        if let (Some(_), Some(host)) = (&endpoint, &properties.host) {
+            return Err(syn::Error::new(host.span(), "`endpoint` and `host` cannot be used together"));
+        }
         Ok(Some(RequestRouteAttributes {
             method: properties.method,
+            endpoint,
+            host: properties.host,
             path: properties.path,
-            headers: properties.headers,
+            headers,
+            header_policy: properties.header_policy,
+            error_body: properties.error_body,
+            not_found_as_none: properties.not_found_as_none,
+            query: properties.query,
             timeout: properties.timeout,
         }))
     }
@@ -183,28 +439,70 @@ impl crate::parser::Parser<ItemTrait> for MethodMetaParser {
 
             let sig_ident = method.sig.ident.clone();
 
-            let ok_ty = extract_ok_type(&method.sig.output).unwrap_or_else(|| {
-                panic!("Method {} must have return type Result<T, E>", sig_ident)
-            });
-
             // 解析路由属性
             let route = RequestRouteParser::parse(&method.attrs)?;
 
-            // 如果没有路由属性，跳过该方法
+            // 没有路由属性的方法原样保留，由客户端继承其默认实现
             let Some(mut route) = route else {
+                if method.default.is_none() {
+                    return Err(syn::Error::new_spanned(
+                        &method.sig,
+                        format!(
+                            "method `{}` has no route attribute (#[get], #[post], ...) and no default body; \
+                             add a route or provide a default implementation",
+                            sig_ident
+                        ),
+                    ));
+                }
                 continue;
             };
 
+            if let Some(body) = &method.default {
+                return Err(syn::Error::new_spanned(
+                    body,
+                    format!("routed method `{}` must not have a default body; its implementation is generated", sig_ident),
+                ));
+            }
+
+            let ok_ty = extract_ok_type(&method.sig.output).ok_or_else(|| {
+                syn::Error::new_spanned(
+                    &method.sig,
+                    format!("routed method `{}` must return `Result<T, E>`", sig_ident),
+                )
+            })?;
+
             route.set_path_if_none(format!("/{}", &sig_ident.to_string()));
 
+            // 生成的方法体通过 self.core 发送请求
+            if method.sig.receiver().is_none() {
+                return Err(syn::Error::new_spanned(
+                    &method.sig,
+                    "routed methods must take `&self` (or `&mut self`) as the first parameter",
+                ));
+            }
+
             // 解析参数属性
             let params = ParamMetaParser::parse(&method.sig.inputs)?;
 
+            let shape = parse_return_shape(&method.attrs, &ok_ty, &method.sig.output)?;
+            let status_arms = parse_status_arms(&method.attrs)?;
+            let builder = match method.attrs.iter().find(|a| a.path().is_ident("builder")) {
+                Some(a) if !matches!(a.meta, Meta::Path(_)) => {
+                    return Err(syn::Error::new_spanned(a, "#[builder] takes no arguments"));
+                }
+                found => found.is_some(),
+            };
+
             out.push(MethodMeta {
                 sig_ident,
                 params,
                 route,
                 ok_ty,
+                sig: method.sig.clone(),
+                attrs: method.attrs.clone(),
+                shape,
+                status_arms,
+                builder,
             })
         }
         Ok(out)
@@ -221,47 +519,60 @@ impl crate::parser::Parser<Punctuated<FnArg, syn::token::Comma>> for ParamMetaPa
             if let FnArg::Typed(pt) = input {
                 let ident = match &*pt.pat {
                     Pat::Ident(pi) => pi.ident.clone(),
-                    _ => panic!("Unsupported parameter pattern"),
+                    _ => {
+                        return Err(syn::Error::new_spanned(
+                            &pt.pat,
+                            "unsupported parameter pattern; use a plain identifier such as `id: u32`",
+                        ))
+                    }
                 };
                 let mut kind = ParamKind::Other;
-                let mut header_name: Option<String> = None;
+                let mut marker: Option<String> = None; // 已出现的参数级属性
                 for a in &pt.attrs {
-                    let name = a
-                        .path()
-                        .get_ident()
-                        .map(|i| i.to_string())
-                        .unwrap_or_default();
-                    match name.as_str() {
-                        "path" => kind = ParamKind::Path,
-                        "query" => {
-                            if let Some(syn::Lit::Str(s)) = a.parse_args().ok() {
-                                kind = ParamKind::Query {
-                                    key: Some(LitStr::new(&s.value(), ident.span())),
-                                };
-                            } else {
-                                kind = ParamKind::Query { key: None };
+                    let Some(name) = a.path().get_ident().map(|i| i.to_string()) else { continue; };
+                    let parsed = match name.as_str() {
+                        "path" | "json" | "headers" | "timeout" | "options" => {
+                            if !matches!(a.meta, Meta::Path(_)) {
+                                return Err(syn::Error::new_spanned(a, format!("#[{}] takes no arguments", name)));
+                            }
+                            match name.as_str() {
+                                "path" => ParamKind::Path,
+                                "json" => ParamKind::Json,
+                                "timeout" => ParamKind::Timeout,
+                                "options" => ParamKind::Options,
+                                _ => ParamKind::Headers,
                             }
                         }
-                        "json" => kind = ParamKind::Json,
+                        "query" => ParamKind::Query {
+                            key: param_attr_name(a, &name)?.map(|s| LitStr::new(&s.value(), ident.span())),
+                        },
+                        "cookie" => ParamKind::Cookie { name: param_attr_name(a, &name)? },
+                        "host_var" => ParamKind::HostVar { name: param_attr_name(a, &name)? },
                         "header" => {
-                            if let Some(syn::Lit::Str(s)) = a.parse_args().ok() {
-                                header_name = Some(s.value());
-                            }
+                            let name = param_attr_name(a, "header")?.ok_or_else(|| {
+                                syn::Error::new_spanned(a, "#[header] requires a header name, e.g. #[header(\"X-Token\")]")
+                            })?;
+                            ParamKind::Header { name: LitStr::new(&name.value(), ident.span()) }
                         }
-                        _ => {}
+                        _ => continue,
+                    };
+                    if let Some(previous) = &marker {
+                        return Err(syn::Error::new_spanned(
+                            a,
+                            format!(
+                                "conflicting parameter attributes #[{}] and #[{}] on `{}`; use only one",
+                                previous, name, ident
+                            ),
+                        ));
                     }
+                    marker = Some(name);
+                    kind = parsed;
                 }
-                if let Some(h) = header_name {
-                    kind = ParamKind::Header {
-                        name: Some(LitStr::new(&h, ident.span())),
-                    };
+                let cardinality = detect_cardinality(&pt.ty);
+                if let ParamKind::Query { key } = &kind {
+                    check_query_value(&ident, &pt.ty, key.is_some(), &cardinality)?;
                 }
-                params.push(ParamMeta {
-                    ident,
-                    ty: Some((*pt.ty).clone()),
-                    kind,
-                    cardinality: detect_cardinality(&*pt.ty),
-                });
+                params.push(ParamMeta { ident, ty: Some((*pt.ty).clone()), kind, cardinality });
             }
         }
 
@@ -270,6 +581,96 @@ impl crate::parser::Parser<Punctuated<FnArg, syn::token::Comma>> for ParamMetaPa
 }
 
 
+impl ReturnShape {
+    /// 响应体类型
+    pub fn body(&self) -> &Type {
+        match self {
+            ReturnShape::Body(ty) | ReturnShape::Response(ty) | ReturnShape::Tuple { body: ty, .. } => ty,
+        }
+    }
+}
+
+/// 解析 `#[status(...)]`，同一状态码不可重复映射
+fn parse_status_arms(attrs: &[Attribute]) -> syn::Result<Vec<StatusArm>> {
+    let mut arms: Vec<StatusArm> = Vec::new();
+    for attr in attrs.iter().filter(|a| a.path().is_ident("status")) {
+        let parsed = attr.parse_args_with(Punctuated::<StatusArm, Comma>::parse_terminated)?;
+        for arm in parsed {
+            if let Some(code) = arm.codes.iter().find(|c| arms.iter().any(|a| a.codes.contains(c))) {
+                return Err(syn::Error::new(arm.variant.span(), format!("status {} is mapped more than once", code)));
+            }
+            arms.push(arm);
+        }
+    }
+    Ok(arms)
+}
+
+/// 根据返回类型与 `#[response_header("...")]` 确定返回值形态
+fn parse_return_shape(attrs: &[Attribute], ok_ty: &TokenStream, output: &syn::ReturnType) -> syn::Result<ReturnShape> {
+    let ok_ty: Type = syn::parse2(ok_ty.clone())?;
+    let names = attrs
+        .iter()
+        .filter(|a| a.path().is_ident("response_header"))
+        .map(|a| a.parse_args::<LitStr>())
+        .collect::<syn::Result<Vec<_>>>()?;
+    if names.is_empty() {
+        return Ok(match extract_response_type(&ok_ty) {
+            Some(body) => ReturnShape::Response(body.clone()),
+            None => ReturnShape::Body(ok_ty),
+        });
+    }
+    // 元组首个元素为响应体，其余依次对应各 #[response_header]
+    match &ok_ty {
+        Type::Tuple(tuple) if tuple.elems.len() == names.len() + 1 => {
+            let mut elems = tuple.elems.iter().cloned();
+            let body = elems.next().expect("tuple has a body element");
+            Ok(ReturnShape::Tuple { body, headers: names.into_iter().zip(elems).collect() })
+        }
+        _ => Err(syn::Error::new(
+            output.span(),
+            format!(
+                "#[response_header] requires the return type to be a tuple of the body and {} header value(s)",
+                names.len()
+            ),
+        )),
+    }
+}
+
+/// 按单值序列化的 `#[query]` 参数（显式命名或 Vec/切片的元素）不可为可静态确定的非标量类型
+fn check_query_value(ident: &syn::Ident, ty: &Type, named: bool, cardinality: &Cardinality) -> syn::Result<()> {
+    let value = match cardinality {
+        Cardinality::Single if named => Some(ty),
+        Cardinality::Option if named => first_generic_arg(ty),
+        Cardinality::Many => collection_element(ty),
+        _ => None,
+    };
+    match value {
+        Some(value) if is_non_scalar_type(value) => Err(syn::Error::new_spanned(
+            value,
+            match cardinality {
+                Cardinality::Many => format!(
+                    "#[query] parameter `{}` repeats its key for each element, so elements must serialize to a single value; nested collections and tuples are not supported",
+                    ident
+                ),
+                _ => format!(
+                    "#[query(\"...\")] parameter `{}` must serialize to a single value; remove the explicit name and use #[query] to expand a struct or map into multiple query parameters",
+                    ident
+                ),
+            },
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Vec<T>、&[T]、[T] 的元素类型
+fn collection_element(ty: &Type) -> Option<&Type> {
+    match ty {
+        Type::Reference(r) => collection_element(&r.elem),
+        Type::Slice(s) => Some(&s.elem),
+        _ => first_generic_arg(ty),
+    }
+}
+
 fn detect_cardinality(ty: &Type) -> Cardinality {
     fn last_ident_of_path(ty: &Type) -> Option<&syn::Ident> {
         let Type::Path(tp) = ty else { return None; };
@@ -298,58 +699,254 @@ fn detect_cardinality(ty: &Type) -> Cardinality {
     }
 }
 
-fn get_parser<'a>(builder: &'a mut AttributePropertiesBuilder) -> impl Parser<Output = ()> + 'a {
+/// 静态键值对 `key = "value"` 或 `"key" = "value"`
+struct StaticPair {
+    key: String,
+    value: String,
+}
+
+impl syn::parse::Parse for StaticPair {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let key = if input.peek(LitStr) {
+            input.parse::<LitStr>()?.value()
+        } else {
+            input.call(syn::Ident::parse_any)?.to_string()
+        };
+        input.parse::<syn::Token![=]>()?;
+        let value: LitStr = input.parse()?;
+        Ok(StaticPair { key, value: value.value() })
+    }
+}
+
+fn validate_endpoint(lit: &LitStr) -> syn::Result<()> {
+    let endpoint = lit.value();
+    // 模板端点：占位符可能位于端口或 scheme 中，只校验占位符与其外的文本
+    let result = if crate::util::template_placeholders(&endpoint).is_empty() {
+        url::Url::parse(&endpoint).map(|_| ()).map_err(|e| e.to_string())
+    } else {
+        crate::util::validate_template(&endpoint)
+    };
+    result.map_err(|e| syn::Error::new(lit.span(), format!("invalid endpoint `{}`: {}", endpoint, e)))
+}
+
+fn get_parser<'a>(
+    builder: &'a mut AttributePropertiesBuilder,
+    keys: &'static [&'static str],
+) -> impl Parser<Output = ()> + 'a {
+    let mut seen: Vec<String> = Vec::new();
     syn::meta::parser(move |meta| {
         let path = meta.path.clone();
-        let ident_str = path.get_ident().map(|i| i.to_string().to_lowercase());
-
-        match ident_str.as_deref() {
-            Some("endpoint") => {
-                let lit: LitStr = meta.value()?.parse()?;
-                builder.endpoint(Some(lit.value()));
+        let Some(key) = path.get_ident().map(|i| i.to_string().to_lowercase()) else {
+            return Err(syn::Error::new_spanned(&path, "expected an attribute key such as `path = \"...\"`"));
+        };
+        check_key(&path, &key, keys, &mut seen)?;
+
+        match key.as_str() {
+            "endpoint" => {
+                let value: AttrValue = parse_value(&meta, "a string literal or env(\"NAME\"), e.g. endpoint = \"https://api.example.com\"")?;
+                // 字面量端点（含 env 默认值）在编译期校验
+                match &value {
+                    AttrValue::Literal(lit) | AttrValue::Env { default: Some(lit), .. } => validate_endpoint(lit)?,
+                    AttrValue::Env { default: None, .. } => {}
+                }
+                builder.endpoint(Some(value));
+            }
+            "hosts" => {
+                let mut hosts: Vec<(String, AttrValue)> = Vec::new();
+                meta.parse_nested_meta(|nested| {
+                    let name = nested
+                        .path
+                        .get_ident()
+                        .ok_or_else(|| nested.error("expected a host name, e.g. hosts(api = \"https://...\")"))?
+                        .to_string();
+                    if hosts.iter().any(|(existing, _)| *existing == name) {
+                        return Err(nested.error(format!("host `{}` is declared more than once", name)));
+                    }
+                    let value: AttrValue = nested.value()?.parse()?;
+                    if let AttrValue::Literal(lit) | AttrValue::Env { default: Some(lit), .. } = &value {
+                        validate_endpoint(lit)?;
+                    }
+                    hosts.push((name, value));
+                    Ok(())
+                })?;
+                builder.hosts(hosts);
+            }
+            "method" => {
+                let lit: LitStr = parse_value(&meta, "a string literal, e.g. method = \"PROPFIND\"")?;
+                let method = Method::from_str(&lit.value()).map_err(|e| syn::Error::new(lit.span(), e))?;
+                builder.method(Some(method));
             }
-            Some("path") => {
-                let lit: LitStr = meta.value()?.parse()?;
+            "host" => {
+                let lit: LitStr = parse_value(&meta, "a host name declared in hosts(...), e.g. host = \"uploads\"")?;
+                builder.host(Some(lit));
+            }
+            "path" => {
+                let lit: LitStr = parse_value(&meta, "a string literal, e.g. path = \"/users/{id}\"")?;
                 builder.path(Some(lit.value()));
             }
-            Some("headers") => {
+            "headers" => {
                 let mut headers = Vec::new();
                 meta.parse_nested_meta(|nested| {
                     let key = nested
                         .path
                         .get_ident()
-                        .ok_or_else(|| nested.error("Expected identifier"))?
+                        .ok_or_else(|| nested.error("expected a header name, e.g. headers(x_token = \"...\")"))?
                         .to_string();
-                    let val: LitStr = nested.value()?.parse()?;
-                    headers.push((key, val.value()));
+                    let val: AttrValue = nested.value()?.parse()?;
+                    headers.push((crate::util::normalize_header_name(&key), val));
                     Ok(())
                 })?;
-                // 判断是否有User-Agent头，如果没有则添加默认头
-                let has_user_agent = headers
-                    .iter()
-                    .any(|(k, _)| k.to_lowercase() == DEFAULT_USER_AGENT_KEY);
-                if !has_user_agent {
-                    headers.push((
-                        DEFAULT_USER_AGENT_KEY.to_string(),
-                        DEFAULT_USER_AGENT_VALUE.to_string(),
-                    ));
-                }
                 builder.headers(headers);
             }
-            Some("timeout") => {
-                let lit: LitInt = meta.value()?.parse()?;
-                builder.timeout(Some(lit.base10_parse()?));
+            "header_policy" => {
+                let lit: LitStr = parse_value(&meta, "\"override\" or \"append\"")?;
+                let policy = match lit.value().as_str() {
+                    "override" => HeaderPolicy::Override,
+                    "append" => HeaderPolicy::Append,
+                    other => {
+                        return Err(syn::Error::new(
+                            lit.span(),
+                            format!("unknown header_policy `{}`, expected \"override\" or \"append\"", other),
+                        ))
+                    }
+                };
+                builder.header_policy(Some(policy));
             }
-            Some("connect_timeout") => {
-                let lit: LitInt = meta.value()?.parse()?;
-                builder.connect_timeout(Some(lit.base10_parse()?));
+            "error_body" => {
+                let ty: Type = parse_value(&meta, "a type, e.g. error_body = ApiError")?;
+                builder.error_body(Some(ty));
             }
-            Some("read_timeout") => {
-                let lit: LitInt = meta.value()?.parse()?;
-                builder.read_timeout(Some(lit.base10_parse()?));
+            mode @ ("send" | "dyn") => {
+                if meta.input.peek(syn::Token![=]) || meta.input.peek(syn::token::Paren) {
+                    return Err(meta.error(format!("`{}` is a flag and takes no value", mode)));
+                }
+                let mode = if mode == "send" { FutureMode::Send } else { FutureMode::Dyn };
+                if matches!(builder.future_mode, Some(Some(existing)) if existing != mode) {
+                    return Err(meta.error("`send` and `dyn` cannot be used together"));
+                }
+                builder.future_mode(Some(mode));
             }
-            _ => {}
+            "not_found_as_none" => {
+                // 支持 `not_found_as_none` 与 `not_found_as_none = true/false`
+                let value = if meta.input.peek(syn::Token![=]) {
+                    parse_value::<syn::LitBool>(&meta, "a boolean, e.g. not_found_as_none = true")?.value
+                } else {
+                    true
+                };
+                builder.not_found_as_none(value);
+            }
+            "query" => {
+                // query(api_version = "...", "api-version" = "...")：键可为标识符或字符串
+                if !meta.input.peek(syn::token::Paren) {
+                    return Err(meta.error("`query` expects a list, e.g. query(api_version = \"2024-01-01\")"));
+                }
+                let content;
+                syn::parenthesized!(content in meta.input);
+                let pairs = Punctuated::<StaticPair, Comma>::parse_terminated(&content)?;
+                builder.query(pairs.into_iter().map(|p| (p.key, p.value)).collect());
+            }
+            "timeout" => {
+                builder.timeout(Some(parse_duration_ms(&meta)?));
+            }
+            "connect_timeout" => {
+                builder.connect_timeout(Some(parse_duration_ms(&meta)?));
+            }
+            "read_timeout" => {
+                builder.read_timeout(Some(parse_duration_ms(&meta)?));
+            }
+            _ => unreachable!("keys are validated by check_key"),
         }
         Ok(())
     })
 }
+
+/// 校验属性键：未知键给出最接近的候选，仅在另一层级可用的键说明其位置，同一属性中的重复键报错
+fn check_key(path: &syn::Path, key: &str, keys: &[&str], seen: &mut Vec<String>) -> syn::Result<()> {
+    if !keys.contains(&key) {
+        let message = if matches!(key, "connect_timeout" | "read_timeout") {
+            format!(
+                "`{}` applies to the whole connection pool in reqwest and is only supported in the trait-level \
+                 #[request(...)] attribute; use `timeout` (or a #[timeout] parameter) per method",
+                key
+            )
+        } else if TRAIT_KEYS.contains(&key) {
+            format!("`{}` is only supported in the trait-level #[request(...)] attribute", key)
+        } else if key == "method" {
+            "`method` is only supported in #[http(...)]; use #[get], #[post], ... for standard methods".to_string()
+        } else if ROUTE_KEYS.contains(&key) {
+            format!("`{}` is only supported in method-level route attributes such as #[get(...)]", key)
+        } else {
+            match crate::util::suggest(key, keys) {
+                Some(candidate) => format!("unknown attribute `{}`, did you mean `{}`?", key, candidate),
+                None => format!("unknown attribute `{}`, expected one of: {}", key, keys.join(", ")),
+            }
+        };
+        return Err(syn::Error::new_spanned(path, message));
+    }
+    // send/dyn 的互斥在解析时单独报错
+    if seen.iter().any(|k| k == key) {
+        return Err(syn::Error::new_spanned(path, format!("duplicate attribute `{}`", key)));
+    }
+    seen.push(key.to_string());
+    Ok(())
+}
+
+/// 解析 `key = value` 的值，缺少 `=` 或类型不符时给出期望的写法
+fn parse_value<T: syn::parse::Parse>(meta: &syn::meta::ParseNestedMeta, expected: &str) -> syn::Result<T> {
+    let key = meta.path.get_ident().map(|i| i.to_string()).unwrap_or_default();
+    if !meta.input.peek(syn::Token![=]) {
+        return Err(meta.error(format!("`{}` expects {}", key, expected)));
+    }
+    let input = meta.value()?;
+    let span = input.span();
+    input
+        .parse::<T>()
+        .map_err(|_| syn::Error::new(span, format!("`{}` expects {}", key, expected)))
+}
+
+/// 参数级属性的可选名称：`#[query]` 或 `#[query("page")]`，名称须为字符串字面量
+fn param_attr_name(attr: &Attribute, name: &str) -> syn::Result<Option<LitStr>> {
+    match &attr.meta {
+        Meta::Path(_) => Ok(None),
+        Meta::List(list) => list.parse_args::<LitStr>().map(Some).map_err(|_| {
+            syn::Error::new_spanned(
+                &list.tokens,
+                format!("#[{}(...)] expects a string literal name, e.g. #[{}(\"name\")]", name, name),
+            )
+        }),
+        Meta::NameValue(nv) => Err(syn::Error::new_spanned(
+            nv,
+            format!("expected #[{}] or #[{}(\"name\")]", name, name),
+        )),
+    }
+}
+
+/// 超时取值的写法提示
+const DURATION_HINT: &str = "a duration such as \"1500ms\", \"10s\" or \"2m\" (a plain integer is milliseconds)";
+
+/// 解析超时：整数按毫秒，字符串或带后缀的整数须带单位，如 `"1500ms"`、`"10s"`、`"2m"`
+fn parse_duration_ms(meta: &syn::meta::ParseNestedMeta) -> syn::Result<u64> {
+    let key = meta.path.get_ident().map(|i| i.to_string()).unwrap_or_default();
+    let lit: syn::Lit = parse_value(meta, DURATION_HINT)?;
+    let (value, span) = match &lit {
+        syn::Lit::Int(int) if int.suffix().is_empty() => return int.base10_parse(),
+        syn::Lit::Int(int) => (int.to_string(), int.span()),
+        syn::Lit::Str(s) => (s.value(), s.span()),
+        _ => return Err(syn::Error::new(lit.span(), format!("`{}` expects {}", key, DURATION_HINT))),
+    };
+    let ms = waygate_shared::parse_duration(&value).and_then(|d| u64::try_from(d.as_millis()).ok());
+    ms.ok_or_else(|| {
+        syn::Error::new(span, format!("invalid duration `{}` for `{}`; expected {}", value, key, DURATION_HINT))
+    })
+}
+
+/// 同一方法只能声明一个路由属性
+fn check_single_route(attr: &Attribute, routed: bool) -> syn::Result<()> {
+    if routed {
+        return Err(syn::Error::new_spanned(
+            attr,
+            "a method can only have one route attribute (#[get], #[post], ..., #[http])",
+        ));
+    }
+    Ok(())
+}
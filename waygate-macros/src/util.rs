@@ -1,22 +1,209 @@
 //! 宏内部的通用工具。
 //!
-//! - 提取 `Result<T, E>` 的 `T`
+//! - 提取 `Result<T, E>` 的 `T`，以及 `waygate::Response<T>` 的 `T`
+//! - 识别返回 `impl Future` / `Pin<Box<dyn Future>>` 的非 async 方法签名
+//! - 判断宏自身的方法级与参数级属性
 //! - 将字符串 HTTP 方法名映射为 `reqwest::Method` 代码片段
+//! - 列出端点模板中的 `{name}` 占位符
+//! - 规范化请求头名称
+//! - 为拼写错误的属性键给出最接近的候选
 
-use syn::{ReturnType, Type};
+use syn::{Attribute, ReturnType, Type};
+
+/// 方法级路由相关属性，展开后从 trait 与 impl 中移除
+const ROUTE_ATTRS: &[&str] = &[
+    "get", "post", "put", "delete", "patch", "head", "options", "trace", "http", "response_header", "status",
+    "builder",
+];
+
+/// 参数级标注属性
+const PARAM_ATTRS: &[&str] = &["path", "query", "json", "header", "headers", "cookie", "host_var", "timeout", "options"];
+
+pub fn is_route_attr(attr: &Attribute) -> bool {
+    attr.path().get_ident().is_some_and(|id| ROUTE_ATTRS.iter().any(|n| id == n))
+}
+
+pub fn is_param_attr(attr: &Attribute) -> bool {
+    attr.path().get_ident().is_some_and(|id| PARAM_ATTRS.iter().any(|n| id == n))
+}
+
+/// 非 async 方法返回的 Future 形态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FutureKind {
+    Async, // async fn
+    Impl,  // fn ... -> impl Future<Output = T>
+    Boxed, // fn ... -> Pin<Box<dyn Future<Output = T>>>
+}
+
+/// 判断方法签名的 Future 形态
+pub fn future_kind(sig: &syn::Signature) -> FutureKind {
+    if sig.asyncness.is_some() {
+        return FutureKind::Async;
+    }
+    match &sig.output {
+        ReturnType::Type(_, ty) if matches!(&**ty, Type::Path(tp) if tp.path.segments.last().is_some_and(|s| s.ident == "Pin")) => {
+            FutureKind::Boxed
+        }
+        _ => FutureKind::Impl,
+    }
+}
+
+/// 去掉 `impl Future<Output = T>`、`Pin<Box<dyn Future<Output = T>>>` 外层，返回 `T`
+pub fn future_output(ty: &Type) -> Option<&Type> {
+    fn from_bounds<'a>(bounds: impl Iterator<Item = &'a syn::TypeParamBound>) -> Option<&'a Type> {
+        for bound in bounds {
+            let syn::TypeParamBound::Trait(tb) = bound else { continue; };
+            let seg = tb.path.segments.last()?;
+            if seg.ident != "Future" {
+                continue;
+            }
+            let syn::PathArguments::AngleBracketed(ab) = &seg.arguments else { return None; };
+            return ab.args.iter().find_map(|arg| match arg {
+                syn::GenericArgument::AssocType(assoc) if assoc.ident == "Output" => Some(&assoc.ty),
+                _ => None,
+            });
+        }
+        None
+    }
+    fn first_type_arg(seg: &syn::PathSegment) -> Option<&Type> {
+        let syn::PathArguments::AngleBracketed(ab) = &seg.arguments else { return None; };
+        ab.args.iter().find_map(|arg| match arg {
+            syn::GenericArgument::Type(t) => Some(t),
+            _ => None,
+        })
+    }
+    match ty {
+        Type::ImplTrait(it) => from_bounds(it.bounds.iter()),
+        Type::TraitObject(to) => from_bounds(to.bounds.iter()),
+        // Pin<Box<dyn Future<Output = T> + Send + 'a>>
+        Type::Path(tp) => {
+            let seg = tp.path.segments.last()?;
+            if seg.ident != "Pin" && seg.ident != "Box" {
+                return None;
+            }
+            future_output(first_type_arg(seg)?)
+        }
+        _ => None,
+    }
+}
 
 /// 从返回类型中提取 `Result<T, E>` 的 `T`。
+///
+/// 也识别 `anyhow::Result<T>`、`AppResult<T>` 等以 `Result` 结尾的别名（取第一个类型参数）。
 pub fn extract_ok_type(ret: &ReturnType) -> Option<proc_macro2::TokenStream> {
     let ReturnType::Type(_, ty) = ret else { return None; };
-    if let Type::Path(tp) = &**ty {
-        let seg = tp.path.segments.last()?;
-        if seg.ident == "Result" {
-            if let syn::PathArguments::AngleBracketed(ab) = &seg.arguments {
-                if let Some(syn::GenericArgument::Type(t)) = ab.args.first() {
-                    return Some(quote::quote! { #t });
-                }
-            }
-        }
+    let ty = future_output(ty).unwrap_or(ty);
+    let Type::Path(tp) = ty else { return None; };
+    let seg = tp.path.segments.last()?;
+    if seg.ident.to_string().ends_with("Result")
+        && let syn::PathArguments::AngleBracketed(ab) = &seg.arguments
+        && let Some(syn::GenericArgument::Type(t)) = ab.args.first()
+    {
+        return Some(quote::quote! { #t });
+    }
+    None
+}
+
+/// 识别 `Response<T>` 与 `waygate::Response<T>`，返回其中的 `T`；
+/// 其他路径下的同名类型（如 `api::Response<T>`）视为普通响应体
+pub fn extract_response_type(ty: &Type) -> Option<&Type> {
+    let Type::Path(tp) = ty else { return None; };
+    let seg = tp.path.segments.last()?;
+    let is_waygate_path = match tp.path.segments.len() {
+        1 => tp.path.leading_colon.is_none(),
+        2 => tp.path.segments[0].ident == "waygate",
+        _ => false,
+    };
+    if is_waygate_path
+        && tp.qself.is_none()
+        && seg.ident == "Response"
+        && let syn::PathArguments::AngleBracketed(ab) = &seg.arguments
+        && ab.args.len() == 1
+        && let Some(syn::GenericArgument::Type(t)) = ab.args.first()
+    {
+        return Some(t);
     }
     None
-}
\ No newline at end of file
+}
+
+/// 是否为 `Option<T>`
+pub fn is_option_type(ty: &Type) -> bool {
+    let Type::Path(tp) = ty else { return false; };
+    tp.path.segments.last().is_some_and(|seg| seg.ident == "Option")
+}
+
+/// 是否为已知标量类型（String/&str/数字/bool/char）
+pub fn is_scalar_type(ty: &Type) -> bool {
+    match ty {
+        Type::Path(tp) => tp.path.segments.last().is_some_and(|seg| {
+            matches!(
+                seg.ident.to_string().as_str(),
+                "String" | "bool" | "char" |
+                "i8" | "i16" | "i32" | "i64" | "i128" | "isize" |
+                "u8" | "u16" | "u32" | "u64" | "u128" | "usize" |
+                "f32" | "f64"
+            )
+        }),
+        Type::Reference(r) => {
+            matches!(&*r.elem, Type::Path(tp) if tp.path.is_ident("str"))
+        }
+        _ => false,
+    }
+}
+
+/// 是否可静态确定为非标量类型（元组、数组/切片、标准库集合与映射），这类值无法序列化为单个查询值；
+/// 自定义类型无法在宏中判断，由运行时 `to_scalar` 检查
+pub fn is_non_scalar_type(ty: &Type) -> bool {
+    match ty {
+        Type::Reference(r) => is_non_scalar_type(&r.elem),
+        Type::Paren(p) => is_non_scalar_type(&p.elem),
+        Type::Tuple(t) => !t.elems.is_empty(),
+        Type::Array(_) | Type::Slice(_) => true,
+        Type::Path(tp) => tp.path.segments.last().is_some_and(|seg| {
+            matches!(
+                seg.ident.to_string().as_str(),
+                "Vec" | "VecDeque" | "LinkedList" | "HashMap" | "BTreeMap" | "HashSet" | "BTreeSet" | "IndexMap" |
+                "IndexSet"
+            )
+        }),
+        _ => false,
+    }
+}
+
+/// 取泛型内层类型（Option<T>/Vec<T>）
+pub fn first_generic_arg(ty: &Type) -> Option<&Type> {
+    let Type::Path(tp) = ty else { return None; };
+    let syn::PathArguments::AngleBracketed(args) = &tp.path.segments.last()?.arguments else {
+        return None;
+    };
+    args.args.iter().find_map(|arg| match arg {
+        syn::GenericArgument::Type(t) => Some(t),
+        _ => None,
+    })
+}
+
+pub use waygate_shared::normalize_header_name;
+pub use waygate_shared::template::{placeholders as template_placeholders, validate as validate_template};
+
+/// 在候选中查找与 `name` 编辑距离最近（不超过 2）的一项，用于 "did you mean" 提示
+pub fn suggest<'a>(name: &str, candidates: &[&'a str]) -> Option<&'a str> {
+    candidates
+        .iter()
+        .map(|c| (edit_distance(name, c), *c))
+        .filter(|(d, _)| *d <= 2)
+        .min_by_key(|(d, _)| *d)
+        .map(|(_, c)| c)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut cur = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            cur[j + 1] = (prev[j] + usize::from(ca != *cb)).min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        prev = cur;
+    }
+    prev[b.len()]
+}
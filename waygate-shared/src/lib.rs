@@ -0,0 +1,28 @@
+//! 运行时与过程宏共用的解析规则
+//!
+//! `waygate` 在运行时、`waygate-macros` 在编译期按同样的规则解析时长、端点模板与请求头名称，
+//! 两者都依赖本 crate，保证宏在编译期接受的写法在运行时同样有效。本 crate 不依赖其他 crate。
+
+pub mod template;
+
+use std::time::Duration;
+
+/// 解析带单位的时长：`"1500ms"`、`"10s"`、`"2m"`、`"1h"`；缺少单位、格式错误或溢出时为 `None`
+pub fn parse_duration(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    let split = value.find(|c: char| !c.is_ascii_digit())?;
+    let (number, unit) = value.split_at(split);
+    let number: u64 = number.parse().ok()?;
+    match unit.trim() {
+        "ms" => Some(Duration::from_millis(number)),
+        "s" => Some(Duration::from_secs(number)),
+        "m" => number.checked_mul(60).map(Duration::from_secs),
+        "h" => number.checked_mul(3600).map(Duration::from_secs),
+        _ => None,
+    }
+}
+
+/// 规范化请求头名称：`_` 替换为 `-` 并转为小写
+pub fn normalize_header_name(name: &str) -> String {
+    name.replace('_', "-").to_ascii_lowercase()
+}
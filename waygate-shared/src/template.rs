@@ -0,0 +1,59 @@
+//! 端点模板 `https://{region}.api.example.com/{tenant}` 的语法
+
+/// 是否包含 `{name}` 占位符
+pub fn is_template(s: &str) -> bool {
+    s.find('{').is_some_and(|start| s[start..].contains('}'))
+}
+
+/// 按出现顺序列出占位符名称
+pub fn placeholders(template: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let Some(len) = rest[start..].find('}') else { break; };
+        names.push(&rest[start + 1..start + len]);
+        rest = &rest[start + len + 1..];
+    }
+    names
+}
+
+/// 校验模板本身：占位符名称由字母、数字与 `_` 组成，占位符之外的文本不含 URL 不允许的字符，
+/// 以字面量开头时须带合法的 scheme；占位符可位于 scheme、主机、端口或路径中，完整 URL 待代入后再解析
+pub fn validate(template: &str) -> Result<(), String> {
+    let mut literals = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find(['{', '}']) {
+        if rest[start..].starts_with('}') {
+            return Err("unmatched `}`".to_string());
+        }
+        let Some(len) = rest[start..].find('}') else {
+            return Err("unclosed `{`".to_string());
+        };
+        let name = &rest[start + 1..start + len];
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(format!("invalid placeholder `{{{}}}`", name));
+        }
+        literals.push(&rest[..start]);
+        rest = &rest[start + len + 1..];
+    }
+    literals.push(rest);
+    if let Some(c) = literals
+        .iter()
+        .flat_map(|literal| literal.chars())
+        .find(|c| c.is_whitespace() || c.is_control() || "<>\"\\^`|".contains(*c))
+    {
+        return Err(format!("invalid character {:?}", c));
+    }
+    if !template.starts_with('{') {
+        let Some((scheme, _)) = literals[0].split_once(':') else {
+            return Err("missing URL scheme".to_string());
+        };
+        let mut chars = scheme.chars();
+        if !chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+            || !chars.all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+        {
+            return Err(format!("invalid URL scheme `{}`", scheme));
+        }
+    }
+    Ok(())
+}
@@ -1,8 +1,10 @@
 use serde::{Deserialize, Serialize};
 
 pub mod macros;
+pub mod server;
 
 #[derive(Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
 pub struct HttpBinGet {
     pub url: String,
     pub args: serde_json::Value,
@@ -10,7 +12,80 @@ pub struct HttpBinGet {
 }
 
 #[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
 pub struct SearchQuery {
     pub q: String,
     pub page: u32,
-}
\ No newline at end of file
+}
+/// 本地回显服务的响应
+#[derive(Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub struct Echo {
+    pub method: String,
+    pub url: String,
+    pub path: String,
+    pub args: serde_json::Value,
+    pub headers: serde_json::Value,
+    pub body: String,
+    pub json: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub struct UserId(pub u32);
+
+/// 合作方接口的结构化错误体
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(schemars::JsonSchema))]
+pub struct ApiError {
+    pub code: String,
+    pub message: String,
+}
+
+/// 仅含错误码的错误体，用于方法级 error_body 覆盖；未实现 JsonSchema，OpenAPI 中为空 schema
+#[derive(Debug, Deserialize)]
+pub struct CodeOnly {
+    pub code: String,
+}
+
+/// 按状态码映射的查询结果
+#[derive(Debug)]
+pub enum Lookup {
+    Found(ApiError),
+    Missing,
+    Conflict(CodeOnly),
+}
+
+/// 应用层错误类型，用于自定义错误的返回签名
+#[derive(Debug)]
+pub enum AppError {
+    NotFound,
+    Upstream(waygate::Error),
+}
+
+impl From<waygate::Error> for AppError {
+    fn from(e: waygate::Error) -> Self {
+        match e.status() {
+            Some(status) if status.as_u16() == 404 => AppError::NotFound,
+            _ => AppError::Upstream(e),
+        }
+    }
+}
+
+pub type AppResult<T> = Result<T, AppError>;
+
+/// 与 `waygate::Response` 同名的业务响应包装
+pub mod api {
+    #[derive(Debug, serde::Deserialize)]
+    pub struct Response<T> {
+        pub path: T,
+    }
+}
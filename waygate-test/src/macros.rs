@@ -1,12 +1,13 @@
 use waygate::request;
 
+/// httpbin 接口
 #[allow(async_fn_in_trait)]
 #[request(
     endpoint = "https://httpbin.org",
-    headers(foo = "bar"),
+    headers(foo = "bar", user_agent = "waygate-test"),
     timeout = 10000,
-    connect_timeout = 11000,
-    read_timeout = 12000,
+    connect_timeout = "11s",
+    read_timeout = "12000ms",
 )]
 pub trait Api {
 
@@ -17,11 +18,15 @@ pub trait Api {
     )]
     async fn get(&self, #[query] q: crate::SearchQuery) -> waygate::Result<crate::HttpBinGet>;
 
+    /// 按 id 检索
+    ///
+    /// 查询参数与请求头原样回显。
     #[get(
         path = "/anything/{id}",
         headers(foo = "bar", token_auth = "abcd1234", foo = "override-bar"),
         timeout = 13000,
     )]
+    #[builder]
     async fn search(
         &self,
         #[query] q: crate::SearchQuery,
@@ -30,4 +35,295 @@ pub trait Api {
         #[path] id: u32,
         #[header("X-Trace-Id")] trace: String,
     ) -> waygate::Result<crate::HttpBinGet>;
-}
\ No newline at end of file
+}
+#[allow(async_fn_in_trait)]
+#[request(endpoint = "http://127.0.0.1", query(api_version = "2024-01-01", "format" = "json"))]
+pub trait EchoApi {
+    #[get(path = "/users/{id}/posts")]
+    #[builder]
+    async fn posts(
+        &self,
+        #[path] id: crate::UserId,
+        #[query("sort")] sort: crate::SortOrder,
+        #[query("order")] order: Option<crate::SortOrder>,
+        #[query("tag")] tags: Vec<crate::SortOrder>,
+    ) -> waygate::Result<crate::Echo>;
+
+    #[get(path = "/lookup")]
+    async fn lookup(&self, #[query("q")] q: crate::SearchQuery) -> waygate::Result<crate::Echo>;
+
+    /// 方法级超时可带单位，#[timeout] 参数在调用时覆盖
+    #[get(path = "/delay/{ms}", timeout = "100ms")]
+    async fn delay(&self, #[path] ms: u64, #[timeout] timeout: Option<std::time::Duration>) -> waygate::Result<crate::Echo>;
+
+    /// #[options] 在调用时追加请求头、查询参数或覆盖超时
+    #[get(path = "/delay/{ms}", headers(x_source = "route"), timeout = "100ms")]
+    async fn delay_with(
+        &self,
+        #[path] ms: u64,
+        #[query("page")] page: u32,
+        #[options] opts: Option<&waygate::RequestOptions>,
+    ) -> waygate::Result<crate::Echo>;
+
+    #[get(path = "/headers")]
+    async fn headers(
+        &self,
+        #[header("X-Optional")] optional: Option<String>,
+        #[header("X-Multi")] multi: Vec<String>,
+        #[headers] extra: std::collections::HashMap<String, String>,
+        #[headers] map: waygate::reqwest::header::HeaderMap,
+    ) -> waygate::Result<crate::Echo>;
+
+    #[get(path = "/auth", headers(authorization = "Bearer {token}", tenant = "t-{tenant}"))]
+    async fn auth(&self, token: &str, tenant: crate::UserId) -> waygate::Result<crate::Echo>;
+
+    #[get(path = "/static", query(format = "xml", "page-size" = "20"))]
+    async fn static_query(&self, #[query("q")] q: &str) -> waygate::Result<crate::Echo>;
+
+    #[get(path = "/cookies")]
+    async fn cookies(
+        &self,
+        #[cookie("session")] session: &str,
+        #[cookie] theme: Option<String>,
+    ) -> waygate::Result<crate::Echo>;
+
+    #[get(path = "/cookies/set/token/{token}")]
+    async fn set_cookie(&self, #[path] token: &str) -> waygate::Result<crate::Echo>;
+
+    #[get(path = "/cookies/session/sid/{sid}")]
+    async fn set_session_cookie(&self, #[path] sid: &str) -> waygate::Result<crate::Echo>;
+
+    #[get(path = "/cookies")]
+    async fn jar_cookies(&self) -> waygate::Result<crate::Echo>;
+}
+
+#[allow(async_fn_in_trait)]
+#[request(
+    endpoint = env("WAYGATE_TEST_ENDPOINT", default = "http://localhost:8080"),
+    headers(x_api_key = env("CARGO_PKG_NAME")), // cargo 运行测试时设置，测试中无需修改环境变量
+)]
+pub trait EnvApi {
+    #[get(path = "/ping")]
+    async fn ping(&self) -> waygate::Result<crate::Echo>;
+}
+
+#[allow(async_fn_in_trait)]
+#[request(endpoint = env("WAYGATE_TEST_MISSING_ENDPOINT"))]
+pub trait MissingEnvApi {
+    #[get(path = "/ping")]
+    async fn ping(&self) -> waygate::Result<crate::Echo>;
+}
+
+#[allow(async_fn_in_trait)]
+#[request(hosts(api = "http://127.0.0.1", uploads = "http://127.0.0.1"))]
+pub trait StorageApi {
+    #[get(path = "/objects/{id}")]
+    async fn metadata(&self, #[path] id: u32) -> waygate::Result<crate::Echo>;
+
+    #[put(path = "/objects/{id}", host = "uploads")]
+    async fn upload(&self, #[path] id: u32, #[json] body: serde_json::Value) -> waygate::Result<crate::Echo>;
+
+    #[get(path = "/status", endpoint = "http://127.0.0.1:9")]
+    async fn status(&self) -> waygate::Result<crate::Echo>;
+}
+
+#[allow(async_fn_in_trait)]
+#[request(endpoint = "http://{host}:{port}/tenants/{tenant}/")]
+pub trait TenantApi {
+    #[get(path = "profile")]
+    async fn profile(&self) -> waygate::Result<crate::Echo>;
+
+    #[get(path = "profile")]
+    async fn profile_of(&self, #[host_var] tenant: &str) -> waygate::Result<crate::Echo>;
+
+    #[get(path = "/users")]
+    async fn users(&self) -> waygate::Result<crate::Echo>;
+}
+
+/// 端口占位符在调用时代入
+#[allow(async_fn_in_trait)]
+#[request(endpoint = "http://127.0.0.1:{port}/")]
+pub trait PortApi {
+    #[get(path = "ports")]
+    async fn ports(&self, #[host_var] port: u16) -> waygate::Result<crate::Echo>;
+}
+
+#[allow(async_fn_in_trait)]
+#[request(endpoint = "http://127.0.0.1", headers(X_Tenant = "trait", x_trace = "trait"))]
+pub trait MergeApi {
+    #[get(path = "/override", headers(x_tenant = "method", x_dup = "a", X_DUP = "b"))]
+    async fn overridden(&self, #[header("X_Trace")] trace: Option<String>) -> waygate::Result<crate::Echo>;
+
+    #[get(path = "/append", header_policy = "append", headers(x_tenant = "method", x_dup = "a", x_dup = "b"))]
+    async fn appended(&self, #[header("x-trace")] trace: Option<String>) -> waygate::Result<crate::Echo>;
+}
+
+#[allow(async_fn_in_trait)]
+#[request(endpoint = "http://127.0.0.1")]
+pub trait MethodsApi {
+    #[head(path = "/status/{code}")]
+    async fn exists(&self, #[path] code: u16) -> waygate::Result<()>;
+
+    #[head(path = "/status/{code}")]
+    async fn head_status(&self, #[path] code: u16) -> waygate::Result<waygate::reqwest::StatusCode>;
+
+    #[delete(path = "/status/{code}")]
+    async fn remove(&self, #[path] code: u16) -> waygate::Result<()>;
+
+    #[head(path = "/response-headers")]
+    async fn head_headers(&self, #[query("etag")] etag: &str) -> waygate::Result<waygate::reqwest::header::HeaderMap>;
+
+    #[options(path = "/objects")]
+    async fn allowed(&self) -> waygate::Result<crate::Echo>;
+
+    #[trace(path = "/trace")]
+    async fn trace(&self) -> waygate::Result<crate::Echo>;
+
+    #[http(method = "PROPFIND", path = "/dav/{name}")]
+    async fn propfind(&self, #[path] name: &str, #[header("Depth")] depth: u8) -> waygate::Result<crate::Echo>;
+}
+
+#[allow(async_fn_in_trait)]
+#[request(endpoint = "http://127.0.0.1")]
+pub trait MetaApi {
+    #[get(path = "/response-headers")]
+    async fn with_meta(&self, #[query("etag")] etag: &str) -> waygate::Result<waygate::Response<crate::Echo>>;
+
+    #[get(path = "/response-headers")]
+    #[response_header("X-Total-Count")]
+    #[response_header("ETag")]
+    #[response_header("X-Missing")]
+    async fn page(
+        &self,
+        #[query("x-total-count")] total: u64,
+        #[query("etag")] etag: &str,
+    ) -> waygate::Result<(crate::Echo, u64, String, Option<String>)>;
+
+    /// 用户自定义的同名 `Response<T>` 按普通响应体解码
+    #[get(path = "/envelope")]
+    async fn envelope(&self) -> waygate::Result<crate::api::Response<String>>;
+
+    #[get(path = "/response-headers")]
+    #[response_header("X-Total-Count")]
+    async fn count(&self, #[query("x-total-count")] total: &str) -> waygate::Result<((), u64)>;
+}
+
+#[allow(async_fn_in_trait)]
+#[request(endpoint = "http://127.0.0.1", error_body = crate::ApiError)]
+pub trait PartnerApi {
+    #[get(path = "/ok")]
+    async fn ok(&self) -> waygate::Result<crate::Echo>;
+
+    #[get(path = "/errors/{status}")]
+    async fn fail(
+        &self,
+        #[path] status: u16,
+        #[query("code")] code: &str,
+        #[query("message")] message: &str,
+    ) -> waygate::Result<crate::Echo>;
+
+    #[get(path = "/status/{status}")]
+    async fn opaque(&self, #[path] status: u16) -> waygate::Result<crate::Echo>;
+
+    #[get(path = "/errors/{status}", error_body = crate::CodeOnly)]
+    async fn coded(&self, #[path] status: u16, #[query("code")] code: &str) -> waygate::Result<crate::Echo>;
+
+    #[get(path = "/status/{status}", not_found_as_none)]
+    async fn find(&self, #[path] status: u16) -> waygate::Result<Option<crate::Echo>>;
+
+    #[get(path = "/errors/{status}")]
+    #[status(200 | 201 => Found(crate::ApiError), 404 => Missing, 409 => Conflict(crate::CodeOnly))]
+    async fn lookup(
+        &self,
+        #[path] status: u16,
+        #[query("code")] code: &str,
+        #[query("message")] message: &str,
+    ) -> waygate::Result<crate::Lookup>;
+}
+
+/// 状态码映射的 OpenAPI 响应
+#[allow(async_fn_in_trait)]
+#[request(endpoint = "http://127.0.0.1")]
+pub trait LookupApi {
+    #[get(path = "/errors/{status}")]
+    #[status(200 | 201 => Found(crate::ApiError), 404 => Missing, 409 => Conflict(crate::CodeOnly))]
+    async fn lookup(&self, #[path] status: u16) -> waygate::Result<crate::Lookup>;
+}
+
+#[allow(async_fn_in_trait)]
+#[request(endpoint = "http://127.0.0.1", error_body = crate::ApiError)]
+pub trait AppApi {
+    #[get(path = "/errors/{status}")]
+    async fn fail(&self, #[path] status: u16, #[query("code")] code: &str) -> Result<crate::Echo, crate::AppError>;
+
+    #[get(path = "/items/{id}")]
+    async fn item(&self, #[path] id: u32) -> crate::AppResult<crate::Echo>;
+
+    #[get(path = "/items/{id}")]
+    async fn boxed(&self, #[path] id: u32) -> Result<crate::Echo, Box<dyn std::error::Error + Send + Sync>>;
+}
+
+#[allow(async_fn_in_trait)]
+#[request(endpoint = "http://127.0.0.1")]
+pub trait SignatureApi {
+    /// 泛型、生命周期与 where 子句原样保留
+    #[put(path = "/docs/{id}")]
+    async fn put<'a, B>(&self, #[path] id: &'a str, #[json] body: &'a B) -> waygate::Result<crate::Echo>
+    where
+        B: serde::Serialize + ?Sized;
+
+    #[get(path = "/search")]
+    async fn search(&mut self, #[query("q")] q: impl serde::Serialize) -> waygate::Result<crate::Echo>;
+
+    #[get(path = "/future")]
+    fn future(&self, #[query("n")] n: u32) -> impl std::future::Future<Output = waygate::Result<crate::Echo>> + Send;
+
+    #[cfg(any())]
+    #[get(path = "/disabled")]
+    async fn disabled(&self) -> waygate::Result<crate::Echo>;
+}
+
+#[request(endpoint = "http://127.0.0.1", send)]
+pub trait SendApi {
+    #[get(path = "/users/{id}")]
+    async fn user(&self, #[path] id: u32, #[query("fields")] fields: &str) -> waygate::Result<crate::Echo>;
+}
+
+#[request(endpoint = "http://127.0.0.1", dyn)]
+pub trait DynApi {
+    #[get(path = "/users/{id}")]
+    #[builder]
+    async fn user(&self, #[path] id: u32, #[query("fields")] fields: Option<&str>) -> waygate::Result<crate::Echo>;
+
+    #[post(path = "/docs/{id}")]
+    async fn create<'a>(&self, #[path] id: &'a str, #[json] body: &serde_json::Value) -> waygate::Result<crate::Echo>;
+}
+
+#[allow(async_fn_in_trait)]
+#[request(endpoint = "http://127.0.0.1")]
+pub trait ComposedApi {
+    /// 有默认值的关联项由客户端继承
+    const PAGE_SIZE: u32 = 2;
+
+    #[client(3)]
+    const RETRIES: u32;
+
+    #[client(crate::Echo)]
+    type Item;
+
+    #[get(path = "/items/{id}")]
+    async fn item(&self, #[path] id: u32) -> waygate::Result<Self::Item>;
+
+    /// 无路由属性的默认方法原样保留，可组合路由方法
+    async fn items(&self, ids: &[u32]) -> waygate::Result<Vec<Self::Item>> {
+        let mut out = Vec::with_capacity(ids.len());
+        for id in ids.iter().take(Self::PAGE_SIZE as usize) {
+            out.push(self.item(*id).await?);
+        }
+        Ok(out)
+    }
+
+    fn max_attempts(&self) -> u32 {
+        Self::RETRIES + 1
+    }
+}
@@ -0,0 +1,140 @@
+//! 本地回显服务
+//!
+//! 以 httpbin 风格返回收到的请求（method/url/path/args/headers/body），供离线测试使用。
+//! - `/status/{code}`：以指定状态码返回回显内容
+//! - `/response-headers?k=v`：将查询参数作为响应头返回
+//! - `/cookies/set/{name}/{value}`：写入持久 Cookie（`Set-Cookie`）
+//! - `/errors/{code}?code=..&message=..`：以指定状态码返回 `{"code": .., "message": ..}`
+//! - `/delay/{ms}`：等待指定毫秒后返回
+
+use serde_json::{Map, Value};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use waygate::url::Url;
+
+/// 启动回显服务，返回形如 `http://127.0.0.1:port` 的基础地址
+pub fn spawn() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind echo server");
+    let addr = listener.local_addr().expect("echo server address");
+    std::thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            std::thread::spawn(move || {
+                let _ = handle(stream);
+            });
+        }
+    });
+    format!("http://{}", addr)
+}
+
+fn handle(mut stream: TcpStream) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let target = parts.next().unwrap_or("/").to_string();
+
+    let mut headers: Vec<(String, String)> = Vec::new();
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            break;
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((k, v)) = line.split_once(':') {
+            headers.push((k.trim().to_ascii_lowercase(), v.trim().to_string()));
+        }
+    }
+
+    let length = headers
+        .iter()
+        .find(|(k, _)| k == "content-length")
+        .and_then(|(_, v)| v.parse::<usize>().ok())
+        .unwrap_or(0);
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+    let body = String::from_utf8_lossy(&body).to_string();
+
+    let host = headers
+        .iter()
+        .find(|(k, _)| k == "host")
+        .map(|(_, v)| v.clone())
+        .unwrap_or_default();
+    let url = Url::parse(&format!("http://{}{}", host, target)).expect("request url");
+
+    let mut args = Map::new();
+    for (k, v) in url.query_pairs() {
+        push_value(&mut args, k.to_string(), v.to_string());
+    }
+    let mut header_map = Map::new();
+    for (k, v) in &headers {
+        push_value(&mut header_map, k.clone(), v.clone());
+    }
+
+    let mut status = 200u16;
+    let mut extra_headers = Vec::new();
+    let segments: Vec<&str> = url.path().trim_start_matches('/').split('/').collect();
+    match segments.as_slice() {
+        ["status", code, ..] => status = code.parse().unwrap_or(500),
+        ["response-headers", ..] => {
+            for (k, v) in url.query_pairs() {
+                extra_headers.push((k.to_string(), v.to_string()));
+            }
+        }
+        ["delay", ms, ..] => std::thread::sleep(std::time::Duration::from_millis(ms.parse().unwrap_or(0))),
+        ["cookies", "set", name, value, ..] => {
+            extra_headers.push(("Set-Cookie".to_string(), format!("{}={}; Path=/; Max-Age=3600", name, value)));
+        }
+        ["cookies", "session", name, value, ..] => {
+            extra_headers.push(("Set-Cookie".to_string(), format!("{}={}; Path=/", name, value)));
+        }
+        _ => {}
+    }
+
+    let mut echo = serde_json::json!({
+        "method": method,
+        "url": url.as_str(),
+        "path": url.path(),
+        "args": args,
+        "headers": header_map,
+        "body": body,
+        "json": serde_json::from_str::<Value>(&body).unwrap_or(Value::Null),
+    });
+    if let ["errors", code, ..] = segments.as_slice() {
+        status = code.parse().unwrap_or(500);
+        let arg = |name: &str| url.query_pairs().find(|(k, _)| k == name).map(|(_, v)| v.to_string());
+        echo = serde_json::json!({ "code": arg("code"), "message": arg("message") });
+    }
+    let payload = if method == "HEAD" { String::new() } else { echo.to_string() };
+
+    let mut response = format!(
+        "HTTP/1.1 {} Echo\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n",
+        status,
+        if method == "HEAD" { echo.to_string().len() } else { payload.len() }
+    );
+    for (k, v) in extra_headers {
+        response.push_str(&format!("{}: {}\r\n", k, v));
+    }
+    response.push_str("\r\n");
+    response.push_str(&payload);
+    stream.write_all(response.as_bytes())?;
+    stream.flush()
+}
+
+// 重复出现的键合并为数组
+fn push_value(map: &mut Map<String, Value>, key: String, value: String) {
+    match map.get_mut(&key) {
+        Some(Value::Array(items)) => items.push(Value::String(value)),
+        Some(existing) => {
+            let first = existing.take();
+            *existing = Value::Array(vec![first, Value::String(value)]);
+        }
+        None => {
+            map.insert(key, Value::String(value));
+        }
+    }
+}
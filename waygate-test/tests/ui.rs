@@ -0,0 +1,9 @@
+//! 宏的编译期错误（UI 测试）
+//!
+//! 使用 `TRYBUILD=overwrite cargo test -p waygate-test --test ui` 重新生成 `.stderr` 快照。
+
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
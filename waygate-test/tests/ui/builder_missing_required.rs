@@ -0,0 +1,15 @@
+use waygate::request;
+
+#[allow(async_fn_in_trait)]
+#[request(endpoint = "http://127.0.0.1")]
+pub trait Api {
+    #[get(path = "/users/{id}")]
+    #[builder]
+    async fn user(&self, #[path] id: u32, #[query("fields")] fields: Option<String>) -> waygate::Result<String>;
+}
+
+async fn call(client: &ApiClient) {
+    let _ = client.user_builder().fields("name".to_string()).send().await;
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use waygate::request;
+
+#[allow(async_fn_in_trait)]
+#[request(endpoint = "http://127.0.0.1")]
+pub trait Api {
+    #[get(path = "/users")]
+    async fn users(&self) -> waygate::Result<String>;
+
+    fn page_size(&self) -> u32;
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use waygate::request;
+
+#[allow(async_fn_in_trait)]
+#[request(endpoint = "http://127.0.0.1")]
+pub trait Api {
+    #[post(path = "/orders")]
+    async fn order(&self) -> waygate::Result<String>;
+
+    #[post(path = "/orders/prepare")]
+    async fn prepare_order(&self) -> waygate::Result<String>;
+}
+
+fn main() {}
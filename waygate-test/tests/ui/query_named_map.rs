@@ -0,0 +1,13 @@
+use waygate::request;
+
+#[allow(async_fn_in_trait)]
+#[request(endpoint = "http://127.0.0.1")]
+pub trait Api {
+    #[get(path = "/users")]
+    async fn users(
+        &self,
+        #[query("filter")] filter: std::collections::HashMap<String, String>,
+    ) -> waygate::Result<String>;
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use waygate::request;
+
+#[allow(async_fn_in_trait)]
+#[request(endpoint = "http://127.0.0.1")]
+pub trait Api {
+    #[get(path = "/users")]
+    async fn users(&self, #[query(page)] page: u32) -> waygate::Result<String>;
+}
+
+fn main() {}
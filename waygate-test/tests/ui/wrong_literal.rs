@@ -0,0 +1,10 @@
+use waygate::request;
+
+#[allow(async_fn_in_trait)]
+#[request(endpoint = "http://127.0.0.1")]
+pub trait Api {
+    #[get(path = 42)]
+    async fn users(&self) -> waygate::Result<String>;
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+//! `#[builder]` 生成的请求构造器所用的类型状态
+//!
+//! 必填参数在设置前以 [`Missing`] 占位，全部设置后构造器才提供 `send()`。
+
+/// 尚未设置的必填参数
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Missing;
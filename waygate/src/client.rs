@@ -1,26 +1,61 @@
 use url::Url;
 use reqwest::Client;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::Duration;
 use derive_builder::Builder;
+use serde::Deserialize;
+use crate::param::HeaderPolicy;
+use crate::interceptor::Interceptor;
+use crate::route::RouteInfo;
 
 const DEFAULT_TIMEOUT_SECS: u64 = 6; // 默认请求超时，单位秒
 const DEFAULT_CONNECT_TIMEOUT_SECS: u64 = 6; // 默认连接超时，单位秒
 const DEFAULT_READ_TIMEOUT_SECS: u64 = 6; // 默认读取超时，单位秒
+const UNRESOLVED_ENDPOINT: &str = "about:blank"; // 模板端点尚有占位符未赋值时 `endpoint` 的取值
 // 默认 User-Agent 头 waygate-client/<version>
 const DEFAULT_USER_AGENT: &str = concat!("waygate-client/", env!("CARGO_PKG_VERSION"));
 
-#[derive(Clone, Debug, Builder)]
+/// 可在多个客户端间共享、可持久化为 JSON 的 Cookie 存储
+pub type CookieJar = reqwest_cookie_store::CookieStoreMutex;
+
+/// 客户端配置
+///
+/// 可通过 Builder 构造，也可从 TOML/YAML/JSON 等配置文件反序列化：
+/// 超时以毫秒整数表示，`headers` 可写成表或键值对列表，缺省字段取默认值。
+/// 反序列化经 Builder 完成，端点、主机与模板的校验与代码构造一致。
+#[derive(Clone, Debug, Builder, Deserialize)]
+#[serde(try_from = "crate::config::OptionConfig")]
 pub struct HttpClientOption {
-    #[builder(setter(custom))]
-    pub endpoint: Url,                  // 端点 URL
-    #[builder(default = "Duration::from_secs(DEFAULT_TIMEOUT_SECS)")]
+    #[builder(setter(custom), field(ty = "Option<String>", build = "self.build_endpoint()?"))]
+    pub endpoint: Url,                  // 端点 URL；模板端点为按构造时变量渲染的结果，仍有未赋值的占位符时为 `about:blank`
+    #[builder(setter(custom), field(ty = "()", build = "self.build_endpoint_template()"))]
+    #[builder_field_attr(allow(dead_code))]
+    pub endpoint_template: Option<String>, // 端点模板，如 https://{region}.api.example.com
+    #[builder(setter(custom), field(ty = "Option<String>", build = "self.endpoint_host.clone()"))]
+    pub endpoint_host: Option<String>,  // 端点取自的具名主机，`set_host` 覆盖该主机时同步更新端点
+    #[builder(setter(custom), default)]
+    pub vars: HashMap<String, String>,  // 端点模板变量
+    #[builder(default = "default_timeout()")]
     pub timeout: Duration,              // 可选的请求超时
     #[builder(default = "default_headers()")]
     pub headers: Vec<(String, String)>, // 额外基础请求头
-    #[builder(default = "Duration::from_secs(DEFAULT_READ_TIMEOUT_SECS)")]
+    #[builder(default)]
+    pub header_policy: HeaderPolicy,    // 重复请求头的合并策略，默认后者覆盖
+    #[builder(default = "default_read_timeout()")]
     pub read_timeout: Duration,         // 读取超时
-    #[builder(default = "Duration::from_secs(DEFAULT_CONNECT_TIMEOUT_SECS)")]
+    #[builder(default = "default_connect_timeout()")]
     pub connect_timeout: Duration,      // 连接超时
+    #[builder(setter(custom), field(ty = "Vec<(String, Result<Url, String>)>", build = "self.build_hosts()?"))]
+    pub hosts: HashMap<String, Url>,    // 具名主机，供方法级 host = "..." 使用
+    #[builder(default)]
+    pub cookie_store: bool,             // 是否启用 Cookie 存储
+    #[builder(default, setter(strip_option))]
+    pub cookie_jar: Option<Arc<CookieJar>>, // 共享的 Cookie 存储，设置后即启用
+    #[builder(default, setter(into, strip_option))]
+    pub cookie_file: Option<PathBuf>,   // Cookie 持久化文件（JSON），构造时加载，`save_cookies()` 写回（含会话 Cookie）；设置后即启用
 }
 
 impl HttpClientOption {
@@ -30,29 +65,110 @@ impl HttpClientOption {
 }
 
 impl HttpClientOptionBuilder {
-    fn endpoint_setter(&mut self, endpoint: impl AsRef<str>) -> &mut Self {
-        let endpoint = Url::parse(endpoint.as_ref()).expect(format!("Invalid endpoint '{}'", endpoint.as_ref()).as_str());
-        self.endpoint = Some(endpoint);
+    /// 设置端点（可含 `{name}` 占位符）；解析失败不会 panic，而是在 `build()` 时返回错误
+    pub fn endpoint(&mut self, endpoint: impl AsRef<str>) -> &mut Self {
+        self.endpoint = Some(endpoint.as_ref().to_string());
+        self.endpoint_host = None;
         self
     }
 
-    pub fn endpoint(&mut self, endpoint: impl AsRef<str>) -> &mut Self {
-        let endpoint = Url::parse(endpoint.as_ref()).expect(format!("Invalid endpoint '{}'", endpoint.as_ref()).as_str());
-        self.endpoint = Some(endpoint);
+    /// 以具名主机作为端点（`build()` 时取该主机的地址）；之后调用 [`Self::endpoint`] 会解除关联
+    pub fn endpoint_host(&mut self, name: impl Into<String>) -> &mut Self {
+        self.endpoint = None;
+        self.endpoint_host = Some(name.into());
+        self
+    }
+
+    /// 设置端点模板变量
+    pub fn var(&mut self, name: impl Into<String>, value: impl Into<String>) -> &mut Self {
+        self.vars
+            .get_or_insert_with(HashMap::new)
+            .insert(name.into(), value.into());
         self
     }
+
+    /// 注册具名主机；解析失败在 `build()` 时返回错误
+    pub fn host(&mut self, name: impl Into<String>, endpoint: impl AsRef<str>) -> &mut Self {
+        let endpoint = endpoint.as_ref();
+        self.hosts.push((
+            name.into(),
+            Url::parse(endpoint).map_err(|e| format!("Invalid host endpoint '{}': {}", endpoint, e)),
+        ));
+        self
+    }
+
+    fn build_hosts(&self) -> Result<HashMap<String, Url>, HttpClientOptionBuilderError> {
+        self.hosts
+            .iter()
+            .map(|(name, url)| match url {
+                Ok(url) => Ok((name.clone(), url.clone())),
+                Err(e) => Err(HttpClientOptionBuilderError::ValidationError(e.clone())),
+            })
+            .collect()
+    }
+
+    fn build_endpoint(&self) -> Result<Url, HttpClientOptionBuilderError> {
+        let Some(endpoint) = &self.endpoint else {
+            let Some(name) = &self.endpoint_host else {
+                return Err(derive_builder::UninitializedFieldError::new("endpoint").into());
+            };
+            // 同名主机以最后一次注册为准
+            return match self.hosts.iter().rev().find(|(n, _)| n == name) {
+                Some((_, Ok(url))) => Ok(url.clone()),
+                Some((_, Err(e))) => Err(HttpClientOptionBuilderError::ValidationError(e.clone())),
+                None => Err(HttpClientOptionBuilderError::ValidationError(format!("Unknown endpoint host '{}'", name))),
+            };
+        };
+        let invalid = |e: String| HttpClientOptionBuilderError::ValidationError(format!("Invalid endpoint '{}': {}", endpoint, e));
+        if !crate::template::is_template(endpoint) {
+            return Url::parse(endpoint).map_err(|e| invalid(e.to_string()));
+        }
+        // 模板端点：构造时变量已全部提供则解析渲染结果，否则保留模板，待调用时代入后再解析
+        crate::template::validate(endpoint).map_err(invalid)?;
+        let vars = self.vars.as_ref();
+        match crate::template::render(endpoint, |name| vars.and_then(|v| v.get(name)).cloned()) {
+            Ok(rendered) => Url::parse(&rendered).map_err(|e| invalid(e.to_string())),
+            Err(crate::template::RenderError::Unresolved(_)) => {
+                Url::parse(UNRESOLVED_ENDPOINT).map_err(|e| invalid(e.to_string()))
+            }
+            Err(e) => Err(invalid(e.to_string())),
+        }
+    }
+
+    fn build_endpoint_template(&self) -> Option<String> {
+        self.endpoint
+            .as_ref()
+            .filter(|endpoint| crate::template::is_template(endpoint))
+            .cloned()
+    }
 }
 
 impl HttpClientOption {
-    pub fn with_endpoint(endpoint: impl AsRef<str>) -> HttpClientOption {
+    pub fn with_endpoint(endpoint: impl AsRef<str>) -> crate::error::Result<HttpClientOption> {
+        let endpoint = endpoint.as_ref();
+        if !crate::template::is_template(endpoint) {
+            Url::parse(endpoint)?;
+        }
         HttpClientOption::builder()
-            .endpoint_setter(endpoint)
+            .endpoint(endpoint)
             .build()
-            .unwrap()
+            .map_err(|e| crate::Error::InvalidArgument(format!("Build HttpClientOption failed: {}", e)))
     }
 }
 
-fn default_headers() -> Vec<(String, String)> {
+pub(crate) fn default_timeout() -> Duration {
+    Duration::from_secs(DEFAULT_TIMEOUT_SECS)
+}
+
+pub(crate) fn default_read_timeout() -> Duration {
+    Duration::from_secs(DEFAULT_READ_TIMEOUT_SECS)
+}
+
+pub(crate) fn default_connect_timeout() -> Duration {
+    Duration::from_secs(DEFAULT_CONNECT_TIMEOUT_SECS)
+}
+
+pub(crate) fn default_headers() -> Vec<(String, String)> {
     vec![
         ("User-Agent".to_string(), DEFAULT_USER_AGENT.to_string()),
     ]
@@ -63,68 +179,351 @@ fn build_client(
     timeout: Duration,
     connect_timeout: Duration,
     read_timeout: Duration,
+    cookies: Option<Arc<CookieJar>>,
 ) -> crate::error::Result<Client> {
-    let client = Client::builder()
+    let mut builder = Client::builder()
         .default_headers(headers)
         .timeout(timeout)
         .connect_timeout(connect_timeout)
-        .read_timeout(read_timeout)
-        .build()?;
+        .read_timeout(read_timeout);
+    if let Some(jar) = cookies {
+        builder = builder.cookie_provider(jar);
+    }
 
-    Ok(client)
+    Ok(builder.build()?)
+}
+
+/// 按配置准备 Cookie 存储：优先使用共享存储，存在持久化文件时将其中的 Cookie 合并进来
+///
+/// 共享存储中已有的 Cookie 比文件更新，同名（域名、路径、名称均相同）时保留存储中的值。
+fn prepare_cookie_jar(option: &HttpClientOption) -> crate::error::Result<Option<Arc<CookieJar>>> {
+    if !option.cookie_store && option.cookie_jar.is_none() && option.cookie_file.is_none() {
+        return Ok(None);
+    }
+    let jar = option.cookie_jar.clone().unwrap_or_default();
+    if let Some(path) = option.cookie_file.as_ref().filter(|p| p.exists()) {
+        let reader = std::io::BufReader::new(std::fs::File::open(path)?);
+        let loaded = cookie_store::serde::json::load(reader)
+            .map_err(|e| crate::Error::CookieStore(format!("Failed to load '{}': {}", path.display(), e)))?;
+        let mut store = lock_jar(&jar)?;
+        let cookies = loaded.iter_any().chain(store.iter_any()).cloned().map(Ok::<_, std::convert::Infallible>);
+        let merged = cookie_store::CookieStore::from_cookies(cookies, false);
+        *store = merged.unwrap_or_else(|never| match never {});
+    }
+    Ok(Some(jar))
+}
+
+/// 校验 Cookie 名称（token）与值（cookie-octet，可带一对双引号）
+fn check_cookie_pair(name: &str, value: &str) -> crate::error::Result<()> {
+    let name_ok = !name.is_empty()
+        && name.bytes().all(|b| b.is_ascii_graphic() && !b"()<>@,;:\\\"/[]?={}".contains(&b));
+    let inner = value.strip_prefix('"').and_then(|v| v.strip_suffix('"')).unwrap_or(value);
+    let value_ok = inner.bytes().all(|b| b.is_ascii_graphic() && !b"\",;\\".contains(&b));
+    if !name_ok {
+        return Err(crate::Error::InvalidArgument(format!("invalid cookie name {:?}", name)));
+    }
+    if !value_ok {
+        return Err(crate::Error::InvalidArgument(format!(
+            "invalid value {:?} for cookie `{}`; values must not contain whitespace, '\"', ',', ';' or '\\'",
+            value, name
+        )));
+    }
+    Ok(())
+}
+
+fn lock_jar(jar: &CookieJar) -> crate::error::Result<std::sync::MutexGuard<'_, cookie_store::CookieStore>> {
+    jar.lock()
+        .map_err(|_| crate::Error::CookieStore("cookie store lock poisoned".to_string()))
 }
 
 /// HTTP 客户端封装，基于 reqwest 实现
 pub struct HttpClient {
     inner: Client,
     option: HttpClientOption,
+    headers: reqwest::header::HeaderMap,
+    cookies: Option<Arc<CookieJar>>,
+    interceptors: Vec<Arc<dyn Interceptor>>,
 }
 
 impl HttpClient {
     /// 创建一个新的 HttpClient 实例
     pub fn new(option: HttpClientOption) -> crate::error::Result<Self> {
         let mut headers = reqwest::header::HeaderMap::new();
+        // 名称规范化后按策略合并；值兼容非 ASCII（如中文）
+        let pairs = option
+            .headers
+            .iter()
+            .map(|(name, value)| crate::param::header(name, value))
+            .collect::<crate::error::Result<Vec<_>>>()?;
+        crate::param::merge_headers(&mut headers, pairs, option.header_policy);
 
-        for (header, value) in &option.headers {
-            headers.insert(
-                reqwest::header::HeaderName::from_bytes(header.as_bytes())?,
-                // 兼容非 ASCII 的值（如中文）：优先 from_str，失败则回退到原始字节
-                reqwest::header::HeaderValue::from_str(&value)
-                    .or_else(|_| reqwest::header::HeaderValue::from_bytes(value.as_bytes()))?,
-            );
-        }
-
-        let inner = build_client(headers, option.timeout, option.connect_timeout, option.read_timeout)?;
+        let cookies = prepare_cookie_jar(&option)?;
+        let inner = build_client(
+            headers.clone(),
+            option.timeout,
+            option.connect_timeout,
+            option.read_timeout,
+            cookies.clone(),
+        )?;
 
         Ok(Self {
             inner,
             option,
+            headers,
+            cookies,
+            interceptors: Vec::new(),
         })
     }
 
     pub fn with_endpoint(endpoint: impl AsRef<str>) -> crate::error::Result<Self> {
-        let endpoint = Url::parse(endpoint.as_ref())?;
-        let option = HttpClientOption::with_endpoint(endpoint);
-        Self::new(option)
+        Self::new(HttpClientOption::with_endpoint(endpoint)?)
     }
 
     pub fn from_reqwest(inner: Client, endpoint: impl AsRef<str>) -> crate::error::Result<Self> {
-        let endpoint = Url::parse(endpoint.as_ref())?;
         Ok(Self {
             inner,
-            option: HttpClientOption::with_endpoint(endpoint),
+            option: HttpClientOption::with_endpoint(endpoint)?,
+            headers: reqwest::header::HeaderMap::new(),
+            cookies: None,
+            interceptors: Vec::new(),
+        })
+    }
+
+    /// 将路径拼接到端点上，失败时返回 [`crate::Error::UrlParse`]
+    pub fn join(&self, path: &str) -> crate::error::Result<Url> {
+        self.join_with_vars(path, &[])
+    }
+
+    /// 以调用时变量（优先）与构造时变量渲染端点模板后拼接路径，路径接在模板的路径部分之后；
+    /// 占位符未解析或主机部分的值不合法时返回 [`crate::Error::InvalidArgument`]
+    pub fn join_with_vars(&self, path: &str, vars: &[(&str, String)]) -> crate::error::Result<Url> {
+        let Some(template) = &self.option.endpoint_template else {
+            return Ok(self.option.endpoint.join(path)?);
+        };
+        let rendered = crate::template::render(template, |name| {
+            vars.iter()
+                .find(|(k, _)| *k == name)
+                .map(|(_, v)| v.clone())
+                .or_else(|| self.option.vars.get(name).cloned())
         })
+        .map_err(|e| crate::Error::InvalidArgument(format!("Cannot render endpoint '{}': {}", template, e)))?;
+        // 模板中的路径（如 `/{tenant}`）属于端点本身：路由路径总是拼接在其后，以 `/` 开头也不替换
+        let mut base = Url::parse(&rendered)?;
+        if !base.path().ends_with('/') {
+            base.set_path(&format!("{}/", base.path()));
+        }
+        Ok(base.join(path.trim_start_matches('/'))?)
+    }
+
+    /// 将路径拼接到具名主机上；主机未注册时返回 [`crate::Error::InvalidArgument`]
+    pub fn join_host(&self, name: &str, path: &str) -> crate::error::Result<Url> {
+        let host = self
+            .host(name)
+            .ok_or_else(|| crate::Error::InvalidArgument(format!("Unknown host '{}'", name)))?;
+        Ok(host.join(path)?)
+    }
+
+    pub fn host(&self, name: &str) -> Option<&Url> {
+        self.option.hosts.get(name)
+    }
+
+    /// 注册或覆盖具名主机；该主机即 `endpoint_host` 时一并更新端点
+    pub fn set_host(&mut self, name: impl Into<String>, endpoint: impl AsRef<str>) -> crate::error::Result<()> {
+        let name = name.into();
+        let endpoint = Url::parse(endpoint.as_ref())?;
+        if self.option.endpoint_host.as_ref() == Some(&name) {
+            self.option.endpoint = endpoint.clone();
+        }
+        self.option.hosts.insert(name, endpoint);
+        Ok(())
+    }
+
+    /// 客户端默认请求头（接口级 `headers(...)`），`Append` 策略下与方法级请求头合并
+    pub fn default_headers(&self) -> &reqwest::header::HeaderMap {
+        &self.headers
+    }
+
+    /// 当前使用的 Cookie 存储（未启用时为 `None`）
+    pub fn cookie_jar(&self) -> Option<&Arc<CookieJar>> {
+        self.cookies.as_ref()
+    }
+
+    /// 将 Cookie 存储写回 `cookie_file`
+    ///
+    /// 会话 Cookie（无 `Expires`/`Max-Age`）一并写入，以便重启后沿用登录状态；过期的 Cookie 在加载时丢弃。
+    pub fn save_cookies(&self) -> crate::error::Result<()> {
+        let (Some(jar), Some(path)) = (&self.cookies, &self.option.cookie_file) else {
+            return Err(crate::Error::InvalidArgument(
+                "save_cookies requires both a cookie store and `cookie_file`".to_string(),
+            ));
+        };
+        let mut writer = std::io::BufWriter::new(std::fs::File::create(path)?);
+        cookie_store::serde::json::save_incl_expired_and_nonpersistent(&*lock_jar(jar)?, &mut writer)
+            .map_err(|e| crate::Error::CookieStore(format!("Failed to save '{}': {}", path.display(), e)))
+    }
+
+    /// 合并存储中的 Cookie 与 `#[cookie]` 参数，生成 `Cookie` 请求头
+    ///
+    /// 请求显式携带 `Cookie` 头时 reqwest 不再注入存储中的 Cookie，因此这里一并带上。
+    /// 参数的名称与值按 RFC 6265 校验，含 `;`、`=`（名称中）等会注入其他 Cookie 的字符时返回
+    /// [`crate::Error::InvalidArgument`]。
+    pub fn cookie_header(
+        &self,
+        url: &Url,
+        params: &[(&str, String)],
+    ) -> crate::error::Result<Option<reqwest::header::HeaderValue>> {
+        for (name, value) in params {
+            check_cookie_pair(name, value)?;
+        }
+        let mut pairs = Vec::new();
+        if let Some(jar) = &self.cookies {
+            pairs.extend(
+                lock_jar(jar)?
+                    .get_request_values(url)
+                    .filter(|(name, _)| !params.iter().any(|(p, _)| p == name))
+                    .map(|(name, value)| format!("{}={}", name, value)),
+            );
+        }
+        pairs.extend(params.iter().map(|(name, value)| format!("{}={}", name, value)));
+        if pairs.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(reqwest::header::HeaderValue::from_str(&pairs.join("; "))?))
+    }
+
+    /// 注册拦截器，按注册顺序调用
+    pub fn add_interceptor(&mut self, interceptor: impl Interceptor + 'static) {
+        self.interceptors.push(Arc::new(interceptor));
+    }
+
+    /// 经拦截器发送请求，供宏生成代码使用
+    pub async fn execute(&self, route: &RouteInfo, mut builder: reqwest::RequestBuilder) -> crate::error::Result<reqwest::Response> {
+        for interceptor in &self.interceptors {
+            builder = interceptor.before_send(route, builder)?;
+        }
+        let start = std::time::Instant::now();
+        let result = builder.send().await;
+        for interceptor in &self.interceptors {
+            interceptor.after_response(route, result.as_ref(), start.elapsed());
+        }
+        Ok(result?)
+    }
+
+    /// 构建但不发送请求，供 `prepare_<method>()` 使用
+    ///
+    /// 依次经过拦截器的 `before_send`，并补齐发送时才会由 reqwest 注入的客户端默认请求头与
+    /// Cookie 存储中的 Cookie，使返回的请求与实际发送的一致。
+    pub fn build_request(&self, route: &RouteInfo, mut builder: reqwest::RequestBuilder) -> crate::error::Result<reqwest::Request> {
+        for interceptor in &self.interceptors {
+            builder = interceptor.before_send(route, builder)?;
+        }
+        let mut request = builder.build()?;
+        let headers = request.headers_mut();
+        for name in self.headers.keys() {
+            if !headers.contains_key(name) {
+                for value in self.headers.get_all(name) {
+                    headers.append(name.clone(), value.clone());
+                }
+            }
+        }
+        if !request.headers().contains_key(reqwest::header::COOKIE)
+            && let Some(cookie) = self.cookie_header(request.url(), &[])?
+        {
+            request.headers_mut().insert(reqwest::header::COOKIE, cookie);
+        }
+        Ok(request)
     }
 
     pub fn client(&self) -> &Client {
         &self.inner
     }
 
+    /// 端点 URL；模板端点为按构造时变量渲染的结果，仍有未赋值的占位符时为 `about:blank`，
+    /// 此时可通过 [`Self::endpoint_template`] 取得模板
     pub fn endpoint(&self) -> &Url {
         &self.option.endpoint
     }
 
+    /// 端点模板，如 `https://{region}.api.example.com`；端点不含占位符时为 `None`
+    pub fn endpoint_template(&self) -> Option<&str> {
+        self.option.endpoint_template.as_deref()
+    }
+
     pub fn options(&self) -> &HttpClientOption {
         &self.option
     }
-}
\ No newline at end of file
+}
+
+/// 生成客户端的构造器：在接口声明的默认配置之上覆盖端点、变量、主机与超时
+///
+/// `#[request]` 为每个 `XxxClient` 生成 `XxxClient::builder()`。
+pub struct ClientBuilder<C> {
+    option: crate::error::Result<HttpClientOptionBuilder>,
+    interceptors: Vec<Arc<dyn Interceptor>>,
+    _client: PhantomData<fn() -> C>,
+}
+
+impl<C: From<HttpClient>> ClientBuilder<C> {
+    pub fn new(option: crate::error::Result<HttpClientOptionBuilder>) -> Self {
+        Self { option, interceptors: Vec::new(), _client: PhantomData }
+    }
+
+    /// 直接修改底层 [`HttpClientOptionBuilder`]
+    pub fn configure(mut self, f: impl FnOnce(&mut HttpClientOptionBuilder)) -> Self {
+        if let Ok(option) = &mut self.option {
+            f(option);
+        }
+        self
+    }
+
+    pub fn endpoint(self, endpoint: impl AsRef<str>) -> Self {
+        self.configure(|o| {
+            o.endpoint(endpoint);
+        })
+    }
+
+    pub fn var(self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.configure(|o| {
+            o.var(name, value);
+        })
+    }
+
+    pub fn host(self, name: impl Into<String>, endpoint: impl AsRef<str>) -> Self {
+        self.configure(|o| {
+            o.host(name, endpoint);
+        })
+    }
+
+    pub fn timeout(self, timeout: Duration) -> Self {
+        self.configure(|o| {
+            o.timeout(timeout);
+        })
+    }
+
+    pub fn connect_timeout(self, timeout: Duration) -> Self {
+        self.configure(|o| {
+            o.connect_timeout(timeout);
+        })
+    }
+
+    pub fn read_timeout(self, timeout: Duration) -> Self {
+        self.configure(|o| {
+            o.read_timeout(timeout);
+        })
+    }
+
+    /// 注册拦截器，见 [`HttpClient::add_interceptor`]
+    pub fn interceptor(mut self, interceptor: impl Interceptor + 'static) -> Self {
+        self.interceptors.push(Arc::new(interceptor));
+        self
+    }
+
+    pub fn build(self) -> crate::error::Result<C> {
+        let option = self.option?
+            .build()
+            .map_err(|e| crate::Error::InvalidArgument(format!("Build HttpClientOption failed: {}", e)))?;
+        let mut client = HttpClient::new(option)?;
+        client.interceptors = self.interceptors;
+        Ok(C::from(client))
+    }
+}
@@ -8,4 +8,87 @@ pub trait Decoder<T> {
 // 预留：json/text/bytes/stream 解码器实现
 // pub struct JsonDecoder(reqwest::Response);
 // pub struct TextDecoder(reqwest::Response);
-// pub struct BytesDecoder(reqwest::Response);
\ No newline at end of file
+// pub struct BytesDecoder(reqwest::Response);
+
+// 错误消息中响应体片段的最大长度
+const ERROR_SNIPPET_LIMIT: usize = 512;
+
+/// 将非 2xx 响应解码为 `error_body` 声明的类型并返回 [`crate::Error::Api`]；
+/// 解码失败时回退为附带响应体片段的 [`crate::Error::Http`]
+pub async fn error_from_response<E>(resp: reqwest::Response) -> crate::Error
+where
+    E: serde::de::DeserializeOwned + std::fmt::Debug + Send + Sync + 'static,
+{
+    let url = resp.url().to_string();
+    let status = resp.status();
+    let text = match resp.text().await {
+        Ok(text) => text,
+        Err(e) => return e.into(),
+    };
+    match serde_json::from_str::<E>(&text) {
+        Ok(body) => crate::Error::Api {
+            url,
+            status,
+            body: crate::error::ErrorBody::new(body),
+        },
+        Err(_) => crate::Error::Http {
+            url,
+            status,
+            body_snippet: crate::error::snippet(&text, ERROR_SNIPPET_LIMIT),
+        },
+    }
+}
+
+/// 状态码未在 `#[status(...)]` 中映射时，返回附带响应体片段的 [`crate::Error::Http`]
+pub async fn unexpected_status(resp: reqwest::Response) -> crate::Error {
+    let url = resp.url().to_string();
+    let status = resp.status();
+    match resp.text().await {
+        Ok(text) => crate::Error::Http {
+            url,
+            status,
+            body_snippet: crate::error::snippet(&text, ERROR_SNIPPET_LIMIT),
+        },
+        Err(e) => e.into(),
+    }
+}
+
+
+/// 从无响应体的回复（HEAD）中提取返回值
+///
+/// - `()`：仅校验状态码，非 2xx 返回 [`crate::Error::Http`]
+/// - `StatusCode` / `HeaderMap` / `(StatusCode, HeaderMap)`：原样返回，由调用方判断状态
+pub trait FromHead: Sized {
+    fn from_head(resp: &reqwest::Response) -> crate::Result<Self>;
+}
+
+impl FromHead for () {
+    fn from_head(resp: &reqwest::Response) -> crate::Result<Self> {
+        if resp.status().is_success() {
+            return Ok(());
+        }
+        Err(crate::Error::Http {
+            url: resp.url().to_string(),
+            status: resp.status(),
+            body_snippet: String::new(),
+        })
+    }
+}
+
+impl FromHead for reqwest::StatusCode {
+    fn from_head(resp: &reqwest::Response) -> crate::Result<Self> {
+        Ok(resp.status())
+    }
+}
+
+impl FromHead for reqwest::header::HeaderMap {
+    fn from_head(resp: &reqwest::Response) -> crate::Result<Self> {
+        Ok(resp.headers().clone())
+    }
+}
+
+impl FromHead for (reqwest::StatusCode, reqwest::header::HeaderMap) {
+    fn from_head(resp: &reqwest::Response) -> crate::Result<Self> {
+        Ok((resp.status(), resp.headers().clone()))
+    }
+}
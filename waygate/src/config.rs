@@ -0,0 +1,129 @@
+//! 配置来源
+//!
+//! - 宏生成的 `new_default()` 通过 [`env_var`] 在构造时解析 `env("NAME", default = "...")`
+//! - `HttpClientOption` 的配置文件形式 [`OptionConfig`]（超时为毫秒整数或带单位的字符串、请求头支持表或键值对列表）
+
+use crate::client::{
+    default_connect_timeout, default_headers, default_read_timeout, default_timeout, HttpClientOption,
+    HttpClientOptionBuilderError,
+};
+use crate::param::HeaderPolicy;
+use serde::{Deserialize, Deserializer};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// 读取环境变量；未设置时使用默认值，二者皆无则返回 [`crate::Error::InvalidArgument`]
+pub fn env_var(name: &str, default: Option<&str>) -> crate::Result<String> {
+    match std::env::var(name) {
+        Ok(value) => Ok(value),
+        Err(std::env::VarError::NotPresent) => default.map(str::to_string).ok_or_else(|| {
+            crate::Error::InvalidArgument(format!("environment variable `{}` is not set", name))
+        }),
+        Err(std::env::VarError::NotUnicode(_)) => Err(crate::Error::InvalidArgument(format!(
+            "environment variable `{}` is not valid unicode",
+            name
+        ))),
+    }
+}
+
+/// 配置文件中的 `HttpClientOption`，反序列化后经 Builder 构造
+#[derive(Deserialize)]
+pub(crate) struct OptionConfig {
+    endpoint: String,
+    #[serde(default)]
+    vars: HashMap<String, String>,
+    #[serde(default = "default_timeout", deserialize_with = "deserialize_duration")]
+    timeout: Duration,
+    #[serde(default, deserialize_with = "deserialize_headers")]
+    headers: Vec<(String, String)>,
+    #[serde(default)]
+    header_policy: HeaderPolicy,
+    #[serde(default = "default_read_timeout", deserialize_with = "deserialize_duration")]
+    read_timeout: Duration,
+    #[serde(default = "default_connect_timeout", deserialize_with = "deserialize_duration")]
+    connect_timeout: Duration,
+    #[serde(default)]
+    hosts: HashMap<String, String>,
+    #[serde(default)]
+    cookie_store: bool,
+    #[serde(default)]
+    cookie_file: Option<PathBuf>,
+}
+
+impl TryFrom<OptionConfig> for HttpClientOption {
+    type Error = HttpClientOptionBuilderError;
+
+    fn try_from(config: OptionConfig) -> Result<Self, Self::Error> {
+        let mut builder = HttpClientOption::builder();
+        builder
+            .endpoint(&config.endpoint)
+            .timeout(config.timeout)
+            .header_policy(config.header_policy)
+            .read_timeout(config.read_timeout)
+            .connect_timeout(config.connect_timeout)
+            .cookie_store(config.cookie_store);
+        for (name, value) in config.vars {
+            builder.var(name, value);
+        }
+        for (name, endpoint) in config.hosts {
+            builder.host(name, endpoint);
+        }
+        if let Some(path) = config.cookie_file {
+            builder.cookie_file(path);
+        }
+        // 与 `#[request(headers(...))]` 一致：未配置的默认请求头（User-Agent）保留
+        let mut headers: Vec<(String, String)> = default_headers()
+            .into_iter()
+            .filter(|(name, _)| !config.headers.iter().any(|(n, _)| n.eq_ignore_ascii_case(name)))
+            .collect();
+        headers.extend(config.headers);
+        builder.headers(headers).build()
+    }
+}
+
+/// 解析带单位的时长：`"1500ms"`、`"10s"`、`"2m"`、`"1h"`；缺少单位或格式错误返回 [`crate::Error::InvalidArgument`]
+pub fn parse_duration(value: &str) -> crate::Result<Duration> {
+    waygate_shared::parse_duration(value).ok_or_else(|| {
+        crate::Error::InvalidArgument(format!(
+            "invalid duration `{}`; expected a number with unit ms, s, m or h, e.g. \"1500ms\"",
+            value
+        ))
+    })
+}
+
+/// 反序列化 `Duration`：整数按毫秒，字符串按 [`parse_duration`] 解析
+fn deserialize_duration<'de, D>(deserializer: D) -> Result<Duration, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Value {
+        Millis(u64),
+        Text(String),
+    }
+
+    match Value::deserialize(deserializer)? {
+        Value::Millis(ms) => Ok(Duration::from_millis(ms)),
+        Value::Text(text) => parse_duration(&text).map_err(serde::de::Error::custom),
+    }
+}
+
+/// 请求头既可写成表 `{ "x-token" = "..." }`，也可写成键值对列表 `[["x-token", "..."]]`
+fn deserialize_headers<'de, D>(deserializer: D) -> Result<Vec<(String, String)>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Headers {
+        Map(std::collections::BTreeMap<String, String>),
+        List(Vec<(String, String)>),
+    }
+
+    Ok(match Headers::deserialize(deserializer)? {
+        Headers::Map(map) => map.into_iter().collect(),
+        Headers::List(list) => list,
+    })
+}
@@ -1,3 +1,5 @@
+use std::any::Any;
+use std::fmt;
 use thiserror::Error;
 
 /// 定义错误类型
@@ -15,6 +17,14 @@ pub enum Error {
         body_snippet: String,
     },
 
+    // 非 2xx 且响应体按 error_body 声明的类型解码成功，可通过 `api_body::<E>()` 取回
+    #[error("API error: {status} for URL: {url}\nResponse body: {body:?}")]
+    Api {
+        url: String,
+        status: reqwest::StatusCode,
+        body: ErrorBody,
+    },
+
     // JSON(或其它) 解析错误，附带 URL、源错误以及响应体片段
     #[error("Failed to parse response from URL: {url}\nSource error: {source}\nResponse body (truncated): {body_snippet}")]
     Decode {
@@ -24,6 +34,13 @@ pub enum Error {
         body_snippet: String,
     },
 
+    // 响应头缺失或无法转换为目标类型，附带响应头名称与原因
+    #[error("Invalid response header `{name}`: {message}")]
+    ResponseHeader {
+        name: String,
+        message: String,
+    },
+
     // URL 解析错误，附带源错误
     #[error("Invalid URL: {0}")]
     UrlParse(#[from] url::ParseError),
@@ -40,6 +57,14 @@ pub enum Error {
     #[error("Invalid argument: {0}")]
     InvalidArgument(String),
 
+    // Cookie 文件读写错误
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    // Cookie 存储加载/保存错误，附带描述信息
+    #[error("Cookie store error: {0}")]
+    CookieStore(String),
+
     // Format 错误，附带描述信息
     #[error("Format error: {0}")]
     FormatError(String),
@@ -48,6 +73,57 @@ pub enum Error {
 
 pub type Result<T, E = Error> = std::result::Result<T, E>;
 
+impl Error {
+    /// 取回 [`Error::Api`] 中按 `error_body` 解码的错误体；类型不符或不是该变体时返回 `None`
+    pub fn api_body<E: Any>(&self) -> Option<&E> {
+        match self {
+            Error::Api { body, .. } => body.downcast_ref(),
+            _ => None,
+        }
+    }
+
+    /// 响应状态码（仅 [`Error::Http`] 与 [`Error::Api`]）
+    pub fn status(&self) -> Option<reqwest::StatusCode> {
+        match self {
+            Error::Http { status, .. } | Error::Api { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+}
+
+/// 类型擦除的错误体，由 `error_body = ApiError` 声明的类型解码而来
+pub struct ErrorBody {
+    value: Box<dyn Any + Send + Sync>,
+    debug: String,
+}
+
+impl ErrorBody {
+    pub fn new<E: Any + Send + Sync + fmt::Debug>(value: E) -> Self {
+        Self {
+            debug: format!("{:?}", value),
+            value: Box::new(value),
+        }
+    }
+
+    pub fn downcast_ref<E: Any>(&self) -> Option<&E> {
+        self.value.downcast_ref()
+    }
+
+    /// 取回错误体所有权；类型不符时原样返回
+    pub fn downcast<E: Any>(self) -> std::result::Result<E, Self> {
+        match self.value.downcast::<E>() {
+            Ok(value) => Ok(*value),
+            Err(value) => Err(Self { value, debug: self.debug }),
+        }
+    }
+}
+
+impl fmt::Debug for ErrorBody {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.debug)
+    }
+}
+
 /// 截取响应体的前 N 个字符用于错误消息 避免过长导致日志臃肿
 pub fn snippet(s: &str, limit: usize) -> String {
     const ELLIPSIS: &str = "…";
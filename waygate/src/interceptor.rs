@@ -0,0 +1,21 @@
+//! 请求拦截器
+//!
+//! 通过 [`crate::HttpClient::add_interceptor`] 或 `XxxClient::builder().interceptor(..)` 注册，
+//! 按注册顺序在每次请求发送前后调用，并能看到当前调用的 [`RouteInfo`]，用于签名、日志与指标。
+
+use crate::route::RouteInfo;
+use reqwest::RequestBuilder;
+use std::time::Duration;
+
+pub trait Interceptor: Send + Sync {
+    /// 发送前调用，可修改请求（如签名、追加请求头）；`prepare_<method>()` 同样会经过此处
+    fn before_send(&self, route: &RouteInfo, request: RequestBuilder) -> crate::Result<RequestBuilder> {
+        let _ = route;
+        Ok(request)
+    }
+
+    /// 收到响应头或传输失败后调用，`elapsed` 为发送到收到响应头的耗时
+    fn after_response(&self, route: &RouteInfo, result: Result<&reqwest::Response, &reqwest::Error>, elapsed: Duration) {
+        let _ = (route, result, elapsed);
+    }
+}
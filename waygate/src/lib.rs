@@ -1,6 +1,17 @@
 pub mod error;
 pub mod client;
+pub mod config;
 pub mod codec;
+pub mod param;
+pub mod options;
+pub mod builder;
+pub mod curl;
+pub mod route;
+pub mod interceptor;
+#[cfg(feature = "openapi")]
+pub mod openapi;
+pub mod response;
+pub mod template;
 pub mod prelude;
 
 pub use prelude::*;
@@ -12,4 +23,7 @@ pub use url; // 宏解析 endpoint 时使用
 pub use waygate_macros::request;
 
 #[cfg(feature = "tracing")]
-pub use tracing;
\ No newline at end of file
+pub use tracing;
+
+#[cfg(feature = "openapi")]
+pub use schemars; // 派生 JsonSchema 时可经 #[schemars(crate = "waygate::schemars")] 使用
\ No newline at end of file
@@ -0,0 +1,227 @@
+//! OpenAPI 3.1 文档生成（`openapi` 特性）
+//!
+//! 启用后 `#[request]` 为每个客户端生成 `XxxClient::openapi()`：路径、方法与参数来自 [`RouteInfo`]，
+//! 请求体、响应体与参数的 schema 由 `schemars` 生成并收集到 `components.schemas`；
+//! 未实现 `JsonSchema` 的类型不影响编译，其 schema 为空对象（任意值）。
+
+use crate::route::RouteInfo;
+use schemars::generate::{SchemaGenerator, SchemaSettings};
+use schemars::JsonSchema;
+use serde_json::{json, Map};
+use std::marker::PhantomData;
+
+pub use serde_json::Value;
+
+/// OpenAPI 文档构造器，供宏生成代码使用
+pub struct OpenApi {
+    info: Map<String, Value>,
+    servers: Vec<Value>,
+    paths: Map<String, Value>,
+    generator: SchemaGenerator,
+}
+
+impl OpenApi {
+    pub fn new(title: &str, version: &str) -> Self {
+        let mut info = Map::new();
+        info.insert("title".into(), title.into());
+        info.insert("version".into(), version.into());
+        // JSON Schema 2020-12 即 OpenAPI 3.1 的 schema 方言，引用指向 components.schemas
+        let generator = SchemaSettings::draft2020_12()
+            .with(|s| {
+                s.definitions_path = "/components/schemas".into();
+                s.meta_schema = None;
+            })
+            .into_generator();
+        Self { info, servers: Vec::new(), paths: Map::new(), generator }
+    }
+
+    /// 接口级文档注释
+    pub fn description(mut self, doc: &str) -> Self {
+        if !doc.is_empty() {
+            self.info.insert("description".into(), doc.into());
+        }
+        self
+    }
+
+    /// 字面量端点
+    pub fn server(mut self, url: &str) -> Self {
+        self.servers.push(json!({ "url": url }));
+        self
+    }
+
+    /// 类型的 schema；具名类型以 `$ref` 引用并登记到 `components.schemas`
+    pub fn schema<T: ?Sized + JsonSchema>(&mut self) -> Value {
+        self.generator.subschema_for::<T>().to_value()
+    }
+
+    /// 登记一个操作；OpenAPI 3.1 不支持的扩展方法（如 PROPFIND）被忽略，
+    /// 同一路径与方法已登记时保留先声明的操作
+    pub fn operation(&mut self, route: &RouteInfo, operation: Operation) {
+        let method = route.method.to_ascii_lowercase();
+        if !["get", "put", "post", "delete", "options", "head", "patch", "trace"].contains(&method.as_str()) {
+            return;
+        }
+        let path = if route.path.starts_with('/') { route.path.to_string() } else { format!("/{}", route.path) };
+        let item = self.paths.entry(path).or_insert_with(|| Value::Object(Map::new()));
+        if let Value::Object(item) = item {
+            item.entry(method).or_insert_with(|| operation.into_value(route));
+        }
+    }
+
+    pub fn into_value(mut self) -> Value {
+        let mut doc = json!({
+            "openapi": "3.1.0",
+            "info": self.info,
+            "paths": self.paths,
+        });
+        if !self.servers.is_empty() {
+            doc["servers"] = Value::Array(self.servers);
+        }
+        let schemas = self.generator.take_definitions(true);
+        if !schemas.is_empty() {
+            doc["components"] = json!({ "schemas": schemas });
+        }
+        doc
+    }
+}
+
+/// 单个操作，对应一个路由方法
+#[derive(Default)]
+pub struct Operation {
+    summary: Option<String>,
+    description: Option<String>,
+    parameters: Vec<Value>,
+    request_body: Option<Value>,
+    responses: Map<String, Value>,
+}
+
+impl Operation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 方法的文档注释：首段为 summary，其余为 description
+    pub fn doc(mut self, doc: &str) -> Self {
+        let doc = doc.trim();
+        let (summary, rest) = doc.split_once("\n\n").unwrap_or((doc, ""));
+        if !summary.is_empty() {
+            self.summary = Some(summary.split_whitespace().collect::<Vec<_>>().join(" "));
+        }
+        if !rest.trim().is_empty() {
+            self.description = Some(rest.trim().to_string());
+        }
+        self
+    }
+
+    /// 参数；`location` 为 path、query、header 或 cookie
+    pub fn param(mut self, name: &str, location: &str, required: bool, schema: Value) -> Self {
+        self.parameters.push(json!({
+            "name": name,
+            "in": location,
+            "required": required || location == "path",
+            "schema": schema,
+        }));
+        self
+    }
+
+    /// 未命名的结构体 `#[query]` 参数，字段展开为多个查询参数
+    pub fn query_object(mut self, name: &str, required: bool, schema: Value) -> Self {
+        self.parameters.push(json!({
+            "name": name,
+            "in": "query",
+            "required": required,
+            "style": "form",
+            "explode": true,
+            "schema": schema,
+        }));
+        self
+    }
+
+    /// 静态查询参数，值固定
+    pub fn static_query(self, name: &str, value: &str) -> Self {
+        self.param(name, "query", true, json!({ "type": "string", "const": value }))
+    }
+
+    /// JSON 请求体
+    pub fn json_body(mut self, required: bool, schema: Value) -> Self {
+        self.request_body = Some(json!({
+            "required": required,
+            "content": { "application/json": { "schema": schema } },
+        }));
+        self
+    }
+
+    /// 响应；`status` 为状态码、`2XX` 或 `default`，`schema` 为 None 时无响应体
+    pub fn response(mut self, status: &str, schema: Option<Value>, headers: Vec<(&str, Value)>) -> Self {
+        let description = match status {
+            "2XX" => "Success",
+            "default" => "Error",
+            code => code
+                .parse::<u16>()
+                .ok()
+                .and_then(|code| reqwest::StatusCode::from_u16(code).ok())
+                .and_then(|code| code.canonical_reason())
+                .unwrap_or("Response"),
+        };
+        let mut response = json!({ "description": description });
+        if let Some(schema) = schema {
+            response["content"] = json!({ "application/json": { "schema": schema } });
+        }
+        if !headers.is_empty() {
+            let headers = headers.into_iter().map(|(name, schema)| (name.to_string(), json!({ "schema": schema })));
+            response["headers"] = Value::Object(headers.collect());
+        }
+        self.responses.insert(status.to_string(), response);
+        self
+    }
+
+    fn into_value(self, route: &RouteInfo) -> Value {
+        let mut op = json!({ "operationId": route.name });
+        if let Some(summary) = self.summary {
+            op["summary"] = summary.into();
+        }
+        if let Some(description) = self.description {
+            op["description"] = description.into();
+        }
+        if !self.parameters.is_empty() {
+            op["parameters"] = Value::Array(self.parameters);
+        }
+        if let Some(body) = self.request_body {
+            op["requestBody"] = body;
+        }
+        op["responses"] = Value::Object(self.responses);
+        op
+    }
+}
+
+/// 按类型是否实现 `JsonSchema` 选择 schema：`(&SchemaProbe::<T>::new()).schema(doc)`
+///
+/// 实现时经 [`WithSchema`] 得到真实 schema，否则自动引用回退到 [`WithoutSchema`] 得到空对象。
+pub struct SchemaProbe<T: ?Sized>(PhantomData<T>);
+
+impl<T: ?Sized> SchemaProbe<T> {
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+pub trait WithSchema {
+    fn schema(&self, doc: &mut OpenApi) -> Value;
+}
+
+impl<T: ?Sized + JsonSchema> WithSchema for SchemaProbe<T> {
+    fn schema(&self, doc: &mut OpenApi) -> Value {
+        doc.schema::<T>()
+    }
+}
+
+pub trait WithoutSchema {
+    fn schema(&self, doc: &mut OpenApi) -> Value;
+}
+
+impl<T: ?Sized> WithoutSchema for &SchemaProbe<T> {
+    fn schema(&self, _doc: &mut OpenApi) -> Value {
+        Value::Object(Map::new())
+    }
+}
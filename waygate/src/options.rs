@@ -0,0 +1,81 @@
+//! 单次调用的请求选项
+//!
+//! 方法声明 `#[options] opts: RequestOptions`（或 `Option<RequestOptions>` / `&RequestOptions`）后，
+//! 调用方无需修改 trait 签名即可为某一次请求追加请求头、查询参数或调整超时。
+//! 选项在全部静态与参数阶段之后应用，因此优先级最高。
+
+use crate::param::{header, merge_headers, HeaderPolicy};
+use reqwest::header::HeaderMap;
+use reqwest::RequestBuilder;
+use std::time::Duration;
+
+/// 幂等键请求头名称
+const IDEMPOTENCY_KEY: &str = "idempotency-key";
+
+/// 单次调用的请求选项
+#[derive(Clone, Debug, Default)]
+pub struct RequestOptions {
+    headers: Vec<(String, String)>, // 额外请求头，按方法的合并策略处理同名头
+    query: Vec<(String, String)>,   // 追加的查询参数
+    timeout: Option<Duration>,      // 覆盖本次请求的超时
+}
+
+impl RequestOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 追加请求头；名称按 [`crate::param::normalize_header_name`] 规范化
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// 设置 `Idempotency-Key` 请求头
+    pub fn idempotency_key(self, key: impl Into<String>) -> Self {
+        self.header(IDEMPOTENCY_KEY, key)
+    }
+
+    /// 追加查询参数，位于方法声明的查询参数之后
+    pub fn query(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.query.push((key.into(), value.into()));
+        self
+    }
+
+    /// 覆盖本次请求的超时（含方法级 `timeout` 与 `#[timeout]` 参数）
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// 将选项应用到请求上，供宏生成代码使用
+    ///
+    /// `Override` 时替换已设置的同名请求头，`Append` 时追加；名称或值非法时返回
+    /// [`crate::Error::HeaderName`] / [`crate::Error::HeaderValue`]。
+    pub fn apply(&self, mut req: RequestBuilder, policy: HeaderPolicy) -> crate::Result<RequestBuilder> {
+        let pairs = self
+            .headers
+            .iter()
+            .map(|(name, value)| header(name, value))
+            .collect::<crate::Result<Vec<_>>>()?;
+        match policy {
+            HeaderPolicy::Override => {
+                let mut headers = HeaderMap::new();
+                merge_headers(&mut headers, pairs, policy);
+                req = req.headers(headers);
+            }
+            HeaderPolicy::Append => {
+                for (name, value) in pairs {
+                    req = req.header(name, value);
+                }
+            }
+        }
+        if !self.query.is_empty() {
+            req = req.query(&self.query);
+        }
+        if let Some(timeout) = self.timeout {
+            req = req.timeout(timeout);
+        }
+        Ok(req)
+    }
+}
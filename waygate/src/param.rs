@@ -0,0 +1,305 @@
+//! 参数值序列化
+//!
+//! 宏生成代码通过 [`to_scalar`] 将 `#[path]`、具名 `#[query("...")]` 与 `#[header("...")]` 参数
+//! 按 serde 规则序列化为单个字符串，使 `rename_all` 枚举、newtype、日期等领域类型可直接使用；
+//! `#[headers]` 参数通过 [`HeaderSource`] 展开为多个请求头，并按 [`HeaderPolicy`] 与接口级、方法级请求头合并。
+
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use serde::Deserialize;
+use serde::ser::{Impossible, Serialize, Serializer};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Display;
+use std::hash::BuildHasher;
+
+/// 将参数值序列化为单个标量字符串
+///
+/// 支持字符串、数字、布尔、字符、单元枚举变体与 newtype；
+/// 序列、映射、结构体等无法表示为单值的类型返回 [`crate::Error::InvalidArgument`]。
+pub fn to_scalar<T: Serialize + ?Sized>(name: &str, value: &T) -> crate::Result<String> {
+    value.serialize(ScalarSerializer).map_err(|e| {
+        crate::Error::InvalidArgument(format!(
+            "parameter `{}` cannot be serialized as a single scalar value: {}",
+            name, e.0
+        ))
+    })
+}
+
+/// 同名请求头的合并策略
+///
+/// 来源优先级依次为接口级、方法级、参数级：
+/// - `Override`（默认）：后一来源中出现的名称替换此前的全部值，同一 `headers(...)` 中重复的键取最后一个
+/// - `Append`：保留所有来源的值，作为多个同名请求头发送
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HeaderPolicy {
+    #[default]
+    Override,
+    Append,
+}
+
+/// 规范化请求头名称：`_` 替换为 `-` 并转为小写（与 `#[request]` 在编译期的规则相同）
+pub use waygate_shared::normalize_header_name;
+
+/// 构造请求头；名称或值非法时返回 [`crate::Error::HeaderName`] / [`crate::Error::HeaderValue`]
+///
+/// 名称经 [`normalize_header_name`] 规范化；与 `HttpClient::new` 一致，
+/// 值优先按可见 ASCII 解析，失败则回退到原始字节以兼容非 ASCII 内容。
+pub fn header(name: &str, value: &str) -> crate::Result<(HeaderName, HeaderValue)> {
+    let name = HeaderName::from_bytes(normalize_header_name(name).as_bytes())?;
+    let value = HeaderValue::from_str(value).or_else(|_| HeaderValue::from_bytes(value.as_bytes()))?;
+    Ok((name, value))
+}
+
+/// 将同一来源的一组请求头按策略合并到 `headers`
+///
+/// `Override` 时组内首次出现的名称替换已有值，组内的多个同名值（如 `Vec<T>` 参数）全部保留。
+pub fn merge_headers(
+    headers: &mut HeaderMap,
+    pairs: impl IntoIterator<Item = (HeaderName, HeaderValue)>,
+    policy: HeaderPolicy,
+) {
+    let mut seen: Vec<HeaderName> = Vec::new();
+    for (name, value) in pairs {
+        if policy == HeaderPolicy::Override && !seen.contains(&name) {
+            seen.push(name.clone());
+            headers.insert(name, value);
+        } else {
+            headers.append(name, value);
+        }
+    }
+}
+
+/// 可展开为多个请求头的参数类型，供 `#[headers]` 使用
+pub trait HeaderSource {
+    fn header_pairs(&self) -> crate::Result<Vec<(HeaderName, HeaderValue)>>;
+}
+
+impl<K: AsRef<str>, V: AsRef<str>, S: BuildHasher> HeaderSource for HashMap<K, V, S> {
+    fn header_pairs(&self) -> crate::Result<Vec<(HeaderName, HeaderValue)>> {
+        self.iter().map(|(k, v)| header(k.as_ref(), v.as_ref())).collect()
+    }
+}
+
+impl<K: AsRef<str>, V: AsRef<str>> HeaderSource for BTreeMap<K, V> {
+    fn header_pairs(&self) -> crate::Result<Vec<(HeaderName, HeaderValue)>> {
+        self.iter().map(|(k, v)| header(k.as_ref(), v.as_ref())).collect()
+    }
+}
+
+impl<K: AsRef<str>, V: AsRef<str>> HeaderSource for Vec<(K, V)> {
+    fn header_pairs(&self) -> crate::Result<Vec<(HeaderName, HeaderValue)>> {
+        self.iter().map(|(k, v)| header(k.as_ref(), v.as_ref())).collect()
+    }
+}
+
+impl HeaderSource for HeaderMap {
+    fn header_pairs(&self) -> crate::Result<Vec<(HeaderName, HeaderValue)>> {
+        Ok(self.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
+    }
+}
+
+impl<T: HeaderSource> HeaderSource for Option<T> {
+    fn header_pairs(&self) -> crate::Result<Vec<(HeaderName, HeaderValue)>> {
+        self.as_ref().map_or_else(|| Ok(Vec::new()), T::header_pairs)
+    }
+}
+
+impl<T: HeaderSource + ?Sized> HeaderSource for &T {
+    fn header_pairs(&self) -> crate::Result<Vec<(HeaderName, HeaderValue)>> {
+        (**self).header_pairs()
+    }
+}
+
+/// 标量序列化错误
+#[derive(Debug)]
+pub struct ScalarError(String);
+
+impl Display for ScalarError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for ScalarError {}
+
+impl serde::ser::Error for ScalarError {
+    fn custom<T: Display>(msg: T) -> Self {
+        ScalarError(msg.to_string())
+    }
+}
+
+fn unsupported(kind: &str) -> ScalarError {
+    ScalarError(format!("{} is not supported", kind))
+}
+
+/// 仅接受单值的 serde 序列化器
+struct ScalarSerializer;
+
+impl Serializer for ScalarSerializer {
+    type Ok = String;
+    type Error = ScalarError;
+    type SerializeSeq = Impossible<String, ScalarError>;
+    type SerializeTuple = Impossible<String, ScalarError>;
+    type SerializeTupleStruct = Impossible<String, ScalarError>;
+    type SerializeTupleVariant = Impossible<String, ScalarError>;
+    type SerializeMap = Impossible<String, ScalarError>;
+    type SerializeStruct = Impossible<String, ScalarError>;
+    type SerializeStructVariant = Impossible<String, ScalarError>;
+
+    fn serialize_bool(self, v: bool) -> Result<String, ScalarError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<String, ScalarError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<String, ScalarError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<String, ScalarError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<String, ScalarError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<String, ScalarError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<String, ScalarError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<String, ScalarError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<String, ScalarError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<String, ScalarError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<String, ScalarError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<String, ScalarError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<String, ScalarError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_char(self, v: char) -> Result<String, ScalarError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<String, ScalarError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<String, ScalarError> {
+        Err(unsupported("byte array"))
+    }
+
+    // None 与单元值视为空字符串
+    fn serialize_none(self) -> Result<String, ScalarError> {
+        Ok(String::new())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<String, ScalarError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<String, ScalarError> {
+        Ok(String::new())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<String, ScalarError> {
+        Ok(String::new())
+    }
+
+    // 单元枚举变体使用 serde 重命名后的变体名
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<String, ScalarError> {
+        Ok(variant.to_string())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<String, ScalarError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<String, ScalarError> {
+        Err(unsupported("newtype enum variant"))
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, ScalarError> {
+        Err(unsupported("sequence"))
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, ScalarError> {
+        Err(unsupported("tuple"))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, ScalarError> {
+        Err(unsupported("tuple struct"))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, ScalarError> {
+        Err(unsupported("tuple enum variant"))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, ScalarError> {
+        Err(unsupported("map"))
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, ScalarError> {
+        Err(unsupported("struct"))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, ScalarError> {
+        Err(unsupported("struct enum variant"))
+    }
+}
@@ -1,6 +1,12 @@
 pub use crate::{
     error::{Error, Result},
-    client::{HttpClient, HttpClientOption, HttpClientOptionBuilder}
+    client::{ClientBuilder, CookieJar, HttpClient, HttpClientOption, HttpClientOptionBuilder},
+    param::HeaderPolicy,
+    options::RequestOptions,
+    curl::ToCurl,
+    route::RouteInfo,
+    interceptor::Interceptor,
+    response::Response,
 };
 
 // 若有可选特性，可按需导出
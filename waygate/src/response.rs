@@ -0,0 +1,123 @@
+//! 响应元信息
+//!
+//! - 返回类型写成 [`Response<T>`] 时，生成代码在解码响应体的同时保留状态码、响应头、最终 URL 与耗时
+//! - 方法上的 `#[response_header("X-Total-Count")]` 通过 [`FromResponseHeader`] 将响应头提取到元组返回值中
+
+use reqwest::StatusCode;
+use reqwest::header::{HeaderMap, HeaderValue};
+use std::time::Duration;
+use url::Url;
+
+/// 带元信息的响应
+#[derive(Debug, Clone)]
+pub struct Response<T> {
+    pub status: StatusCode, // 状态码
+    pub headers: HeaderMap, // 响应头
+    pub url: Url,           // 最终 URL（跟随重定向后）
+    pub elapsed: Duration,  // 发送请求到读取完响应体的耗时
+    pub body: T,            // 解码后的响应体
+}
+
+impl<T> Response<T> {
+    /// 按名称读取响应头；不存在或不是可见 ASCII 时返回 `None`
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).and_then(|v| v.to_str().ok())
+    }
+
+    pub fn into_body(self) -> T {
+        self.body
+    }
+
+    /// 转换响应体，保留元信息
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Response<U> {
+        Response {
+            status: self.status,
+            headers: self.headers,
+            url: self.url,
+            elapsed: self.elapsed,
+            body: f(self.body),
+        }
+    }
+}
+
+/// 可从响应头提取的类型，供 `#[response_header("...")]` 使用
+///
+/// 缺失的响应头对 `Option<T>` 为 `None`、对 `Vec<T>` 为空，其余类型返回 [`crate::Error::ResponseHeader`]。
+pub trait FromResponseHeader: Sized {
+    fn from_header_value(name: &str, value: &HeaderValue) -> crate::Result<Self>;
+
+    fn from_headers(name: &str, headers: &HeaderMap) -> crate::Result<Self> {
+        match headers.get(name) {
+            Some(value) => Self::from_header_value(name, value),
+            None => Err(header_error(name, "missing")),
+        }
+    }
+}
+
+fn header_error(name: &str, message: impl Into<String>) -> crate::Error {
+    crate::Error::ResponseHeader {
+        name: name.to_string(),
+        message: message.into(),
+    }
+}
+
+fn header_str<'a>(name: &str, value: &'a HeaderValue) -> crate::Result<&'a str> {
+    value.to_str().map_err(|e| header_error(name, e.to_string()))
+}
+
+impl FromResponseHeader for HeaderValue {
+    fn from_header_value(_name: &str, value: &HeaderValue) -> crate::Result<Self> {
+        Ok(value.clone())
+    }
+}
+
+impl FromResponseHeader for String {
+    fn from_header_value(name: &str, value: &HeaderValue) -> crate::Result<Self> {
+        header_str(name, value).map(str::to_string)
+    }
+}
+
+macro_rules! impl_from_response_header {
+    ($($ty:ty),*) => {
+        $(
+            impl FromResponseHeader for $ty {
+                fn from_header_value(name: &str, value: &HeaderValue) -> crate::Result<Self> {
+                    header_str(name, value)?
+                        .trim()
+                        .parse()
+                        .map_err(|e| header_error(name, format!("{}", e)))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_response_header!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64, bool);
+
+impl<T: FromResponseHeader> FromResponseHeader for Option<T> {
+    fn from_header_value(name: &str, value: &HeaderValue) -> crate::Result<Self> {
+        T::from_header_value(name, value).map(Some)
+    }
+
+    fn from_headers(name: &str, headers: &HeaderMap) -> crate::Result<Self> {
+        headers
+            .get(name)
+            .map(|value| T::from_header_value(name, value))
+            .transpose()
+    }
+}
+
+impl<T: FromResponseHeader> FromResponseHeader for Vec<T> {
+    fn from_header_value(name: &str, value: &HeaderValue) -> crate::Result<Self> {
+        T::from_header_value(name, value).map(|v| vec![v])
+    }
+
+    // 同名响应头逐个转换
+    fn from_headers(name: &str, headers: &HeaderMap) -> crate::Result<Self> {
+        headers
+            .get_all(name)
+            .iter()
+            .map(|value| T::from_header_value(name, value))
+            .collect()
+    }
+}
@@ -0,0 +1,47 @@
+//! 路由元信息
+//!
+//! `#[request]` 为每个生成的客户端输出 `XxxClient::ROUTES`，列出其全部路由方法，可用于文档、权限审计与契约测试；
+//! 发送请求时当前路由会传给 [`crate::Interceptor`]。
+
+use std::time::Duration;
+
+/// 一个路由方法的声明信息
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RouteInfo {
+    pub name: &'static str,                               // trait 方法名
+    pub method: &'static str,                             // HTTP 方法，如 "GET"、"PROPFIND"
+    pub path: &'static str,                               // 路径模板，如 "/users/{id}"
+    pub params: &'static [ParamInfo],                     // 参数（不含 self），按声明顺序
+    pub headers: &'static [(&'static str, &'static str)], // 方法级静态请求头，名称已规范化，值可含 {param} 模板
+    pub timeout: Option<Duration>,                        // 方法级超时
+}
+
+/// 路由方法的参数
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParamInfo {
+    pub name: &'static str,        // 形参名
+    pub kind: ParamKind,           // 参数标注
+    pub key: Option<&'static str>, // 显式声明的名称，如 #[query("page")]、#[header("X-Token")]
+}
+
+/// 参数标注，对应 `#[path]`、`#[query]` 等参数级属性
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParamKind {
+    Path,
+    Query,
+    Json,
+    Header,
+    Headers,
+    Cookie,
+    HostVar,
+    Timeout,
+    Options,
+    Other, // 未标注的参数，如仅被 headers(...) 模板引用
+}
+
+impl RouteInfo {
+    /// 按形参名查找参数
+    pub fn param(&self, name: &str) -> Option<&ParamInfo> {
+        self.params.iter().find(|p| p.name == name)
+    }
+}
@@ -0,0 +1,72 @@
+//! 端点模板
+//!
+//! 形如 `https://{region}.api.example.com/{tenant}` 的端点，占位符在构造时（`var`）
+//! 或调用时（`#[host_var]`）取值，发送前必须全部解析。构造时只校验模板本身，
+//! 代入全部变量后才按 URL 解析。
+
+pub use waygate_shared::template::{is_template, validate};
+
+/// 渲染失败的原因
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RenderError {
+    /// 占位符未赋值
+    Unresolved(String),
+    /// scheme、主机或端口中的值含有会改变 URL 结构的字符
+    InvalidValue { name: String, value: String },
+}
+
+impl std::fmt::Display for RenderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RenderError::Unresolved(name) => write!(f, "unresolved placeholder '{{{}}}'", name),
+            RenderError::InvalidValue { name, value } => write!(
+                f,
+                "value {:?} for placeholder '{{{}}}' must not contain '/', '?', '#', '@', ':', '\\' or whitespace",
+                value, name
+            ),
+        }
+    }
+}
+
+/// 渲染模板
+///
+/// 位于 scheme、主机或端口（首个 `://` 之后第一个 `/`、`?`、`#` 之前）的值不得含有 `/ ? # @ : \` 与空白，
+/// 以免把请求引向其他主机；其后路径中的值按路径片段百分号编码。
+pub fn render(template: &str, mut lookup: impl FnMut(&str) -> Option<String>) -> Result<String, RenderError> {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let Some(len) = rest[start..].find('}') else { break; };
+        let name = &rest[start + 1..start + len];
+        out.push_str(&rest[..start]);
+        let value = lookup(name).ok_or_else(|| RenderError::Unresolved(name.to_string()))?;
+        if in_authority(&out) {
+            if value.chars().any(|c| c.is_whitespace() || "/?#@:\\".contains(c)) {
+                return Err(RenderError::InvalidValue { name: name.to_string(), value });
+            }
+            out.push_str(&value);
+        } else {
+            encode_segment(&mut out, &value);
+        }
+        rest = &rest[start + len + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// 已渲染部分是否仍处于 scheme、主机或端口中；主机部分的值不含 `/ ? # :`，`://` 只可能来自字面量
+fn in_authority(rendered: &str) -> bool {
+    let rest = rendered.find("://").map_or(rendered, |i| &rendered[i + 3..]);
+    !rest.contains(['/', '?', '#'])
+}
+
+/// 按路径片段百分号编码，仅保留非保留字符
+fn encode_segment(out: &mut String, value: &str) {
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) {
+            out.push(byte as char);
+        } else {
+            out.push_str(&format!("%{:02X}", byte));
+        }
+    }
+}